@@ -0,0 +1,60 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use geo_types::Coord;
+
+use h3ron::collections::H3Treemap;
+use h3ron::H3Cell;
+use h3ron_graph::algorithm::shortest_path::{DefaultShortestPathOptions, ShortestPath};
+use h3ron_graph::graph::builder::from_cell_set;
+use h3ron_graph::graph::PreparedH3EdgeGraph;
+
+const H3_RESOLUTION: u8 = 8;
+
+fn build_bench_graph(origin_cell: H3Cell, k: u32) -> PreparedH3EdgeGraph<u32> {
+    let cells: H3Treemap<H3Cell> = origin_cell.grid_disk(k).unwrap().iter().collect();
+    from_cell_set(&cells, H3_RESOLUTION, 1_u32)
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
+
+fn route_to_many_destinations(
+    routing_graph: &PreparedH3EdgeGraph<u32>,
+    origin_cell: H3Cell,
+    destination_cells: &[H3Cell],
+) {
+    let paths = routing_graph
+        .shortest_path(
+            origin_cell,
+            destination_cells,
+            &DefaultShortestPathOptions::default(),
+        )
+        .unwrap();
+    assert_eq!(paths.len(), destination_cells.len());
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let origin_cell = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), H3_RESOLUTION).unwrap();
+    let routing_graph = build_bench_graph(origin_cell, 60);
+
+    // every cell of the graph is a destination
+    let destination_cells: Vec<_> = origin_cell.grid_disk(60).unwrap().iter().collect();
+
+    let mut group = c.benchmark_group("route_many_destinations");
+    group.sample_size(10);
+    group.bench_function(
+        format!("route to {} destinations", destination_cells.len()),
+        |b| {
+            b.iter(|| {
+                route_to_many_destinations(
+                    black_box(&routing_graph),
+                    origin_cell,
+                    black_box(&destination_cells),
+                )
+            })
+        },
+    );
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
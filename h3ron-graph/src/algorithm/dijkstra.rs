@@ -7,7 +7,7 @@ use indexmap::map::IndexMap;
 use num_traits::Zero;
 
 use h3ron::collections::compressed::Decompressor;
-use h3ron::collections::{H3CellMap, H3CellSet, H3Treemap, HashMap, RandomState};
+use h3ron::collections::{H3CellMap, H3Treemap, RandomState};
 use h3ron::{H3Cell, H3DirectedEdge, Index};
 
 use crate::algorithm::path::{DirectedEdgePath, Path};
@@ -65,31 +65,61 @@ struct DijkstraEntry<'a, W> {
     edge: Option<DijkstraEdge<'a>>,
 }
 
-/// follow the edges of the graph until the aggregated weights reach `threshold_weight`.
-/// Returns a hashmap of all traversed cells and the weight.
+/// follow the edges of the graph starting from all `origin_cells` until the aggregated weights
+/// reach `threshold_weight`.
+/// Returns a hashmap of all traversed cells and the weight from the nearest origin cell.
 ///
 /// This function does not make usage of longedges.
 pub fn edge_dijkstra_weight_threshold<G, W>(
     graph: &G,
-    origin_cell: &H3Cell,
+    origin_cells: &[H3Cell],
     threshold_weight: W,
     // TODO: optional bitmap/set of cells we are interested in
 ) -> Result<H3CellMap<W>, Error>
+where
+    G: GetCellEdges<EdgeWeightType = W>,
+    W: Zero + Ord + Copy + Add,
+{
+    Ok(
+        edge_dijkstra_predecessors(graph, origin_cells, Some(threshold_weight))?
+            .drain(..)
+            .map(|(cell, (weight, _))| (cell, weight))
+            .collect(),
+    )
+}
+
+/// follow the edges of the graph starting from all `origin_cells` until the aggregated weights
+/// reach `threshold_weight` or the complete reachable graph has been traversed.
+///
+/// Returns a map of all traversed cells to a tuple of the weight from the nearest origin
+/// cell and the position of the predecessor cell within the map. The position of the
+/// predecessor of the origin cells is `usize::MAX`.
+///
+/// This function does not make usage of longedges.
+pub fn edge_dijkstra_predecessors<G, W>(
+    graph: &G,
+    origin_cells: &[H3Cell],
+    threshold_weight: Option<W>,
+) -> Result<IndexMap<H3Cell, (W, usize), RandomState>, Error>
 where
     G: GetCellEdges<EdgeWeightType = W>,
     W: Zero + Ord + Copy + Add,
 {
     let mut to_see = BinaryHeap::new();
-    let mut parents: IndexMap<H3Cell, W, RandomState> = IndexMap::default();
+    let mut parents: IndexMap<H3Cell, (W, usize), RandomState> = IndexMap::default();
 
-    to_see.push(SmallestHolder {
-        weight: W::zero(),
-        index: 0,
-    });
-    parents.insert(*origin_cell, W::zero());
+    for origin_cell in origin_cells {
+        if let Vacant(e) = parents.entry(*origin_cell) {
+            to_see.push(SmallestHolder {
+                weight: W::zero(),
+                index: e.index(),
+            });
+            e.insert((W::zero(), usize::MAX));
+        }
+    }
 
     while let Some(SmallestHolder { weight, index }) = to_see.pop() {
-        let (cell, weight_from_parents) = parents.get_index(index).unwrap();
+        let (cell, (weight_from_parents, _)) = parents.get_index(index).unwrap();
 
         // We may have inserted a node several time into the binary heap if we found
         // a better way to access it. Ensure that we are currently dealing with the
@@ -104,20 +134,22 @@ where
             let new_weight = weight + succeeding_edge_value.weight;
 
             // skip following this edge when the threshold is reached.
-            if new_weight > threshold_weight {
-                continue;
+            if let Some(threshold_weight) = threshold_weight {
+                if new_weight > threshold_weight {
+                    continue;
+                }
             }
 
             let n;
             match parents.entry(succeeding_edge.destination_cell()?) {
                 Vacant(e) => {
                     n = e.index();
-                    e.insert(new_weight);
+                    e.insert((new_weight, index));
                 }
                 Occupied(mut e) => {
-                    if e.get() > &new_weight {
+                    if e.get().0 > new_weight {
                         n = e.index();
-                        e.insert(new_weight);
+                        e.insert((new_weight, index));
                     } else {
                         continue;
                     }
@@ -129,21 +161,29 @@ where
             });
         }
     }
-    Ok(parents.drain(..).collect())
+    Ok(parents)
 }
 
 /// Dijkstra shortest path using h3 edges
 ///
+/// `on_visit` is called with the cell and its weight each time a cell gets settled. The
+/// cells are visited in the order of their weight, starting with the origin cell.
+///
+/// Edges starting or ending in one of the `avoid_cells` are not traversed.
+///
 /// Adapted from the `run_dijkstra` function of the `pathfinding` crate.
-pub fn edge_dijkstra<G, W>(
+pub fn edge_dijkstra<G, W, V>(
     graph: &G,
     origin_cell: &H3Cell,
     destinations: &H3Treemap<H3Cell>,
     num_destinations_to_reach: Option<usize>,
+    avoid_cells: Option<&H3Treemap<H3Cell>>,
+    mut on_visit: V,
 ) -> Result<Vec<Path<W>>, Error>
 where
     G: GetCellEdges<EdgeWeightType = W>,
     W: Zero + Ord + Copy + Add,
+    V: FnMut(H3Cell, W),
 {
     // this is the main exit condition. Stop after this many destinations have been reached or
     // the complete graph has been traversed.
@@ -153,7 +193,10 @@ where
 
     let mut to_see = BinaryHeap::new();
     let mut parents: IndexMap<H3Cell, DijkstraEntry<W>, RandomState> = IndexMap::default();
-    let mut destinations_reached = H3CellSet::default();
+
+    // positions of the reached destinations in `parents`. Each cell gets settled only once, so
+    // there is no need for a set to de-duplicate the destinations.
+    let mut destinations_reached = Vec::new();
 
     to_see.push(SmallestHolder {
         weight: W::zero(),
@@ -167,14 +210,9 @@ where
             edge: None,
         },
     );
+    let is_avoided = |cell: &H3Cell| avoid_cells.is_some_and(|avoid| avoid.contains(cell));
     while let Some(SmallestHolder { weight, index }) = to_see.pop() {
         let (cell, dijkstra_entry) = parents.get_index(index).unwrap();
-        if destinations.contains(cell)
-            && destinations_reached.insert(*cell)
-            && destinations_reached.len() >= num_destinations_to_reach
-        {
-            break;
-        }
 
         // We may have inserted a node several time into the binary heap if we found
         // a better way to access it. Ensure that we are currently dealing with the
@@ -182,13 +220,32 @@ where
         if weight > dijkstra_entry.weight {
             continue;
         }
+        on_visit(*cell, weight);
+
+        if destinations.contains(cell) {
+            destinations_reached.push(index);
+            if destinations_reached.len() >= num_destinations_to_reach {
+                break;
+            }
+        }
+
+        if is_avoided(cell) {
+            continue;
+        }
 
         for (succeeding_edge, succeeding_edge_value) in graph.get_edges_originating_from(cell)? {
+            if is_avoided(&succeeding_edge.destination_cell()?) {
+                continue;
+            }
+
             // use the longedge if it does not contain any destination. If it would
             // contain a destination we would "jump over" it when we would use the longedge.
+            // The same applies for cells to avoid.
             let (dijkstra_edge, new_weight) =
                 if let Some((longedge, longedge_weight)) = succeeding_edge_value.longedge {
-                    if longedge.is_disjoint(destinations) {
+                    if longedge.is_disjoint(destinations)
+                        && avoid_cells.iter().all(|avoid| longedge.is_disjoint(avoid))
+                    {
                         (DijkstraEdge::Long(longedge), longedge_weight + weight)
                     } else {
                         (
@@ -233,27 +290,13 @@ where
         }
     }
 
-    let parents_map: HashMap<_, _> = parents
-        .iter()
-        .skip(1)
-        .map(|(cell, dijkstra_entry)| {
-            (
-                *cell,
-                (
-                    parents.get_index(dijkstra_entry.index).unwrap().0,
-                    dijkstra_entry,
-                ),
-            )
-        })
-        .collect();
-
-    edge_dijkstra_assemble_paths(origin_cell, parents_map, destinations_reached)
+    edge_dijkstra_assemble_paths(origin_cell, &parents, &destinations_reached)
 }
 
-fn edge_dijkstra_assemble_paths<'a, W>(
+fn edge_dijkstra_assemble_paths<W>(
     origin_cell: &H3Cell,
-    parents_map: HashMap<H3Cell, (&'a H3Cell, &DijkstraEntry<'a, W>)>,
-    destinations_reached: H3CellSet,
+    parents: &IndexMap<H3Cell, DijkstraEntry<W>, RandomState>,
+    destinations_reached: &[usize],
 ) -> Result<Vec<Path<W>>, Error>
 where
     W: Zero + Ord + Copy,
@@ -262,20 +305,20 @@ where
 
     // assemble the paths
     let mut paths = Vec::with_capacity(destinations_reached.len());
-    for destination_cell in destinations_reached {
-        // start from the destination and collect all edges up to the origin
+    for destination_index in destinations_reached {
+        // start from the destination and follow the parents up to the origin. The
+        // origin is the only entry without a valid parent index.
+        let total_weight = parents
+            .get_index(*destination_index)
+            .map_or_else(W::zero, |(_, dijkstra_entry)| dijkstra_entry.weight);
 
         let mut rev_dijkstra_edges: Vec<&DijkstraEdge> = vec![];
-        let mut next = destination_cell;
-        let mut total_weight: Option<W> = None;
-        while let Some((parent_cell, parent_edge_value)) = parents_map.get(&next) {
-            if total_weight.is_none() {
-                total_weight = Some(parent_edge_value.weight);
-            }
-            if let Some(dijkstra_edge) = parent_edge_value.edge.as_ref() {
+        let mut next = *destination_index;
+        while let Some((_, dijkstra_entry)) = parents.get_index(next) {
+            if let Some(dijkstra_edge) = dijkstra_entry.edge.as_ref() {
                 rev_dijkstra_edges.push(dijkstra_edge);
             }
-            next = **parent_cell;
+            next = dijkstra_entry.index;
         }
 
         // reverse order to go from origin to destination
@@ -301,7 +344,7 @@ where
             DirectedEdgePath::DirectedEdgeSequence(h3edges)
         };
 
-        paths.push((path_directed_edges, total_weight.unwrap_or_else(W::zero)).try_into()?);
+        paths.push((path_directed_edges, total_weight).try_into()?);
     }
 
     // return sorted from lowest to highest cost, use destination cell as second criteria
@@ -339,7 +382,48 @@ impl<W: Ord> Ord for SmallestHolder<W> {
 
 #[cfg(test)]
 mod tests {
-    use crate::algorithm::dijkstra::SmallestHolder;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+    use std::convert::TryInto;
+
+    use h3ron::collections::H3Treemap;
+    use h3ron::H3Cell;
+
+    use crate::algorithm::dijkstra::{edge_dijkstra, SmallestHolder};
+    use crate::graph::builder::from_cell_set;
+    use crate::graph::PreparedH3EdgeGraph;
+
+    /// Counts the allocations of the current thread while counting is enabled.
+    struct CountingAllocator;
+
+    thread_local! {
+        static NUM_ALLOCATIONS: Cell<Option<usize>> = const { Cell::new(None) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = NUM_ALLOCATIONS.try_with(|num| {
+                if let Some(n) = num.get() {
+                    num.set(Some(n + 1));
+                }
+            });
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn count_allocations<F: FnOnce() -> R, R>(f: F) -> (usize, R) {
+        NUM_ALLOCATIONS.with(|num| num.set(Some(0)));
+        let result = f();
+        let num_allocations = NUM_ALLOCATIONS.with(|num| num.take()).unwrap();
+        (num_allocations, result)
+    }
 
     #[test]
     fn smallest_holder_partial_eq() {
@@ -366,4 +450,70 @@ mod tests {
         };
         assert!(sh2 > sh1);
     }
+
+    #[test]
+    fn edge_dijkstra_large_destination_set() {
+        let res = 8;
+        let origin = H3Cell::from_coordinate((23.3, 12.3).into(), res).unwrap();
+        let k = 20;
+        let destinations: H3Treemap<H3Cell> = origin.grid_disk(k).unwrap().iter().collect();
+        let graph: PreparedH3EdgeGraph<u32> = from_cell_set(&destinations, res, 1_u32)
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        let paths = edge_dijkstra(&graph, &origin, &destinations, None, None, |_, _| {}).unwrap();
+        assert_eq!(paths.len(), destinations.len());
+        for path in paths.iter() {
+            assert_eq!(
+                path.cost,
+                origin.grid_distance_to(path.destination_cell).unwrap() as u32
+            );
+        }
+        assert!(paths.windows(2).all(|w| w[0].cost <= w[1].cost));
+
+        // stop early after the origin and its direct neighbors have been reached
+        let paths =
+            edge_dijkstra(&graph, &origin, &destinations, Some(7), None, |_, _| {}).unwrap();
+        assert_eq!(paths.len(), 7);
+        assert!(paths.iter().all(|path| path.cost <= 1));
+    }
+
+    #[test]
+    fn edge_dijkstra_allocations_independent_of_destination_set_size() {
+        let res = 8;
+        let origin = H3Cell::from_coordinate((23.3, 12.3).into(), res).unwrap();
+        let cells: H3Treemap<H3Cell> = origin.grid_disk(10).unwrap().iter().collect();
+        let graph: PreparedH3EdgeGraph<u32> = from_cell_set(&cells, res, 1_u32)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let destination = origin.grid_ring_unsafe(10).unwrap().first().unwrap();
+
+        // the same destination, padded with many cells outside of the graph which can
+        // never be reached
+        let small_destinations: H3Treemap<H3Cell> = std::iter::once(destination).collect();
+        let large_destinations: H3Treemap<H3Cell> = std::iter::once(destination)
+            .chain(
+                H3Cell::from_coordinate((-40.1, 50.2).into(), res)
+                    .unwrap()
+                    .grid_disk(40)
+                    .unwrap()
+                    .iter(),
+            )
+            .collect();
+        assert!(large_destinations.len() > 4000);
+
+        let route = |destinations: &H3Treemap<H3Cell>| {
+            count_allocations(|| {
+                edge_dijkstra(&graph, &origin, destinations, Some(1), None, |_, _| {}).unwrap()
+            })
+        };
+        let (small_allocations, small_paths) = route(&small_destinations);
+        let (large_allocations, large_paths) = route(&large_destinations);
+
+        assert_eq!(small_paths, large_paths);
+        assert!(small_allocations > 0);
+        assert_eq!(small_allocations, large_allocations);
+    }
 }
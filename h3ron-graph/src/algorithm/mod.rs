@@ -4,6 +4,7 @@ mod dijkstra;
 pub mod nearest_graph_nodes;
 pub mod path;
 pub mod shortest_path;
+pub mod shortest_path_tree;
 pub mod within_weight_threshold;
 
 // re-export all algorithm traits
@@ -11,4 +12,5 @@ pub use covered_area::CoveredArea;
 pub use differential_shortest_path::DifferentialShortestPath;
 pub use nearest_graph_nodes::NearestGraphNodes;
 pub use shortest_path::{ShortestPath, ShortestPathManyToMany};
+pub use shortest_path_tree::ShortestPathTree;
 pub use within_weight_threshold::{WithinWeightThreshold, WithinWeightThresholdMany};
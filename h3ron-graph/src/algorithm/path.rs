@@ -1,10 +1,11 @@
 use std::cmp::Ordering;
 
+use geo::Simplify;
 use geo_types::LineString;
 use serde::{Deserialize, Serialize};
 
 use h3ron::to_geo::{ToLineString, ToMultiLineString};
-use h3ron::{H3Cell, H3DirectedEdge, Index};
+use h3ron::{H3Cell, H3DirectedEdge, Index, ToCoordinate};
 
 use crate::error::Error;
 
@@ -20,6 +21,12 @@ pub enum DirectedEdgePath {
     ///
     /// The cost is the total cost summed for all of the edges.
     DirectedEdgeSequence(Vec<H3DirectedEdge>),
+
+    /// the destination cell can not be reached from the origin cell.
+    Unreachable {
+        origin_cell: H3Cell,
+        destination_cell: H3Cell,
+    },
 }
 
 impl DirectedEdgePath {
@@ -27,6 +34,7 @@ impl DirectedEdgePath {
         match self {
             Self::OriginIsDestination(_) => true,
             Self::DirectedEdgeSequence(edges) => edges.is_empty(),
+            Self::Unreachable { .. } => true,
         }
     }
 
@@ -35,6 +43,7 @@ impl DirectedEdgePath {
         match self {
             Self::OriginIsDestination(_) => 0,
             Self::DirectedEdgeSequence(edges) => edges.len(),
+            Self::Unreachable { .. } => 0,
         }
     }
 
@@ -48,6 +57,7 @@ impl DirectedEdgePath {
                     Err(Error::EmptyPath)
                 }
             }
+            Self::Unreachable { origin_cell, .. } => Ok(*origin_cell),
         }
     }
 
@@ -61,12 +71,17 @@ impl DirectedEdgePath {
                     Err(Error::EmptyPath)
                 }
             }
+            Self::Unreachable {
+                destination_cell, ..
+            } => Ok(*destination_cell),
         }
     }
 
     pub fn to_linestring(&self) -> Result<LineString<f64>, Error> {
         match self {
-            Self::OriginIsDestination(_) => Err(Error::InsufficientNumberOfEdges),
+            Self::OriginIsDestination(_) | Self::Unreachable { .. } => {
+                Err(Error::InsufficientNumberOfEdges)
+            }
             Self::DirectedEdgeSequence(edges) => match edges.len() {
                 0 => Err(Error::InsufficientNumberOfEdges),
                 1 => Ok(edges[0].to_linestring()?),
@@ -85,7 +100,7 @@ impl DirectedEdgePath {
     pub fn edges(&self) -> &[H3DirectedEdge] {
         match self {
             Self::DirectedEdgeSequence(edges) => edges.as_slice(),
-            Self::OriginIsDestination(_) => &[],
+            Self::OriginIsDestination(_) | Self::Unreachable { .. } => &[],
         }
     }
 
@@ -93,6 +108,7 @@ impl DirectedEdgePath {
     pub fn cells(&self) -> Result<Vec<H3Cell>, Error> {
         match self {
             Self::OriginIsDestination(cell) => Ok(vec![*cell]),
+            Self::Unreachable { .. } => Ok(vec![]),
             Self::DirectedEdgeSequence(edges) => {
                 let mut cells = Vec::with_capacity(edges.len() * 2);
                 for edge in edges.iter() {
@@ -110,7 +126,7 @@ impl DirectedEdgePath {
     /// contained edges
     pub fn length_m(&self) -> Result<f64, Error> {
         match self {
-            Self::OriginIsDestination(_) => Ok(0.0),
+            Self::OriginIsDestination(_) | Self::Unreachable { .. } => Ok(0.0),
             Self::DirectedEdgeSequence(edges) => {
                 let mut length_m = 0.0;
                 for edge in edges {
@@ -137,6 +153,8 @@ pub struct Path<W> {
     /// path may differ in case `destination_cell` is not directly connected to the graph
     pub destination_cell: H3Cell,
 
+    /// The total cost of the path. Unreachable paths have a cost of zero, use
+    /// [`Path::is_reachable`] to tell them apart.
     pub cost: W,
 
     /// describes the path
@@ -153,6 +171,31 @@ impl<W> Path<W> {
     pub fn len(&self) -> usize {
         self.directed_edge_path.len()
     }
+
+    /// Linestring connecting the centroids of the cells of the path, simplified using
+    /// the Douglas-Peucker algorithm with the given `epsilon`.
+    pub fn simplified_linestring(&self, epsilon: f64) -> Result<LineString<f64>, Error> {
+        if self.is_empty() {
+            return Err(Error::InsufficientNumberOfEdges);
+        }
+        let coordinates = self
+            .directed_edge_path
+            .cells()?
+            .iter()
+            .map(|cell| cell.to_coordinate())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(LineString::from(coordinates).simplify(&epsilon))
+    }
+
+    /// `false` when the path describes a destination which can not be reached from
+    /// the origin.
+    #[inline]
+    pub fn is_reachable(&self) -> bool {
+        !matches!(
+            self.directed_edge_path,
+            DirectedEdgePath::Unreachable { .. }
+        )
+    }
 }
 
 impl<W> TryFrom<(DirectedEdgePath, W)> for Path<W> {
@@ -187,7 +230,8 @@ impl Ord for DirectedEdgePath {
     }
 }
 
-/// order by cost, origin index and destination_index.
+/// order by cost, origin index and destination_index. Unreachable paths are
+/// ordered after all reachable paths.
 ///
 /// This ordering can used to bring `Vec`s of routes in a deterministic order to make them
 /// comparable
@@ -196,6 +240,10 @@ where
     W: Ord,
 {
     fn cmp(&self, other: &Self) -> Ordering {
+        let cmp_reachable = other.is_reachable().cmp(&self.is_reachable());
+        if cmp_reachable != Ordering::Equal {
+            return cmp_reachable;
+        }
         let cmp_cost = self.cost.cmp(&other.cost);
         if cmp_cost == Ordering::Equal {
             self.directed_edge_path.cmp(&other.directed_edge_path)
@@ -221,7 +269,9 @@ fn index_or_zero(cell: Result<H3Cell, Error>) -> u64 {
 
 #[cfg(test)]
 mod tests {
-    use h3ron::{H3DirectedEdge, Index};
+    use geo_types::Coord;
+
+    use h3ron::{grid_path_cells, H3Cell, H3DirectedEdge, Index};
 
     use super::{DirectedEdgePath, Path};
 
@@ -263,4 +313,31 @@ mod tests {
         assert_eq!(paths[1], r2);
         assert_eq!(paths[2], r3);
     }
+
+    #[test]
+    fn simplified_linestring() {
+        let cells = grid_path_cells(
+            H3Cell::from_coordinate(Coord::from((23.3, 12.3)), 8).unwrap(),
+            H3Cell::from_coordinate(Coord::from((23.6, 12.8)), 8).unwrap(),
+        )
+        .unwrap()
+        .iter()
+        .collect::<Vec<_>>();
+        let edges = cells
+            .windows(2)
+            .map(|w| w[0].directed_edge_to(w[1]).unwrap())
+            .collect::<Vec<_>>();
+        let path: Path<_> = (DirectedEdgePath::DirectedEdgeSequence(edges), 1)
+            .try_into()
+            .unwrap();
+
+        let full = path.simplified_linestring(0.0).unwrap();
+        assert_eq!(full.0.len(), cells.len());
+
+        let simplified = path.simplified_linestring(0.01).unwrap();
+        assert!(simplified.0.len() >= 2);
+        assert!(simplified.0.len() < full.0.len());
+        assert_eq!(simplified.0.first(), full.0.first());
+        assert_eq!(simplified.0.last(), full.0.last());
+    }
 }
@@ -12,7 +12,7 @@ use h3ron::iter::change_resolution;
 use h3ron::{H3Cell, HasH3Resolution};
 
 use crate::algorithm::dijkstra::edge_dijkstra;
-use crate::algorithm::path::Path;
+use crate::algorithm::path::{DirectedEdgePath, Path};
 use crate::algorithm::NearestGraphNodes;
 use crate::error::Error;
 use crate::graph::{GetCellEdges, GetCellNode};
@@ -36,6 +36,24 @@ pub trait ShortestPathOptions {
     fn num_destinations_to_reach(&self) -> Option<usize> {
         None
     }
+
+    /// Include a [`Path`] for each requested destination which could not be reached from
+    /// the origin. These paths are marked using [`DirectedEdgePath::Unreachable`].
+    ///
+    /// Destinations which are not connected to the graph at all are still filtered out. When
+    /// routing stopped early because of [`ShortestPathOptions::num_destinations_to_reach`], no
+    /// unreachable destinations are reported.
+    fn report_unreachable(&self) -> bool {
+        false
+    }
+
+    /// Cells to route around.
+    ///
+    /// Edges starting or ending in any of these cells are not traversed, so destinations
+    /// which can only be reached through these cells become unreachable.
+    fn avoid_cells(&self) -> Option<&H3Treemap<H3Cell>> {
+        None
+    }
 }
 
 /// Default implementation of a type implementing the `ShortestPathOptions`
@@ -66,7 +84,28 @@ pub trait ShortestPath<W> {
     ) -> Result<Vec<Path<W>>, Error>
     where
         I: IntoIterator,
-        I::Item: Borrow<H3Cell>;
+        I::Item: Borrow<H3Cell>,
+    {
+        self.shortest_path_visit(origin_cell, destination_cells, options, |_, _| {})
+    }
+
+    /// Variant of [`ShortestPath::shortest_path`] calling `on_visit` with the cell and its
+    /// weight each time a cell of the graph gets settled during the routing.
+    ///
+    /// This allows observing the order in which the graph is expanded, for example
+    /// for debugging or visualization.
+    fn shortest_path_visit<I, OPT, V>(
+        &self,
+        origin_cell: H3Cell,
+        destination_cells: I,
+        options: &OPT,
+        on_visit: V,
+    ) -> Result<Vec<Path<W>>, Error>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<H3Cell>,
+        OPT: ShortestPathOptions,
+        V: FnMut(H3Cell, W);
 }
 
 /// Variant of the [`ShortestPath`] trait routing from multiple
@@ -186,6 +225,7 @@ where
                         let origin_cell = path.origin_cell;
                         path_transform_fn(path).map(|transformed| (origin_cell, transformed))
                     },
+                    |_, _| {},
                 )
             })
             .collect::<Result<Vec<_>, _>>()?
@@ -208,16 +248,18 @@ where
     G: GetCellEdges<EdgeWeightType = W> + GetCellNode + HasH3Resolution + NearestGraphNodes,
     W: PartialOrd + PartialEq + Add + Copy + Ord + Zero,
 {
-    fn shortest_path<I, OPT>(
+    fn shortest_path_visit<I, OPT, V>(
         &self,
         origin_cell: H3Cell,
         destination_cells: I,
         options: &OPT,
+        on_visit: V,
     ) -> Result<Vec<Path<W>>, Error>
     where
         I: IntoIterator,
         I::Item: Borrow<H3Cell>,
         OPT: ShortestPathOptions,
+        V: FnMut(H3Cell, W),
     {
         let (graph_connected_origin_cell, requested_origin_cells) = {
             let mut filtered_origin_cells = substitute_origin_cells(
@@ -259,11 +301,13 @@ where
             &destination_substmap,
             options,
             Ok,
+            on_visit,
         )
     }
 }
 
-fn shortest_path_many_worker<G, W, OPT, PM, O>(
+#[allow(clippy::too_many_arguments)]
+fn shortest_path_many_worker<G, W, OPT, PM, O, V>(
     graph: &G,
     origin_cell: &H3Cell,
     requested_origin_cells: &[H3Cell],
@@ -271,6 +315,7 @@ fn shortest_path_many_worker<G, W, OPT, PM, O>(
     destination_substmap: &SubstituteMap,
     options: &OPT,
     path_transform_fn: PM,
+    on_visit: V,
 ) -> Result<Vec<O>, Error>
 where
     G: GetCellEdges<EdgeWeightType = W>,
@@ -278,16 +323,44 @@ where
     PM: Fn(Path<W>) -> Result<O, Error>,
     O: Clone,
     OPT: ShortestPathOptions,
+    V: FnMut(H3Cell, W),
 {
     let found_paths = edge_dijkstra(
         graph,
         origin_cell,
         destination_cells,
         options.num_destinations_to_reach(),
+        options.avoid_cells(),
+        on_visit,
     )?;
 
     let mut transformed_paths = Vec::with_capacity(found_paths.len());
 
+    let routing_stopped_early = options
+        .num_destinations_to_reach()
+        .is_some_and(|num_destinations| found_paths.len() >= num_destinations);
+    if options.report_unreachable() && !routing_stopped_early {
+        let reached: H3Treemap<H3Cell> = found_paths.iter().map(|p| p.destination_cell).collect();
+        for (graph_cell, destination_cells) in destination_substmap.0.iter() {
+            if reached.contains(graph_cell) {
+                continue;
+            }
+            for destination_cell in destination_cells {
+                for origin_cell in requested_origin_cells {
+                    transformed_paths.push(path_transform_fn(Path {
+                        origin_cell: *origin_cell,
+                        destination_cell: *destination_cell,
+                        cost: W::zero(),
+                        directed_edge_path: DirectedEdgePath::Unreachable {
+                            origin_cell: *origin_cell,
+                            destination_cell: *destination_cell,
+                        },
+                    })?);
+                }
+            }
+        }
+    }
+
     for path in found_paths.into_iter() {
         for destination_cell in destination_substmap.cells_substituted_by(&path.destination_cell) {
             for origin_cell in requested_origin_cells {
@@ -419,9 +492,12 @@ mod tests {
 
     use geo_types::Coord;
 
-    use h3ron::H3Cell;
+    use h3ron::collections::H3Treemap;
+    use h3ron::{grid_path_cells, H3Cell};
 
-    use crate::algorithm::shortest_path::{DefaultShortestPathOptions, ShortestPathManyToMany};
+    use crate::algorithm::shortest_path::{
+        DefaultShortestPathOptions, ShortestPath, ShortestPathManyToMany, ShortestPathOptions,
+    };
     use crate::graph::{H3EdgeGraph, PreparedH3EdgeGraph};
 
     #[test]
@@ -462,4 +538,164 @@ mod tests {
             }
         }
     }
+
+    struct ReportUnreachableOptions {}
+
+    impl ShortestPathOptions for ReportUnreachableOptions {
+        fn report_unreachable(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_shortest_path_report_unreachable() {
+        let res = 8;
+        let origin = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), res).unwrap();
+        let edge = origin.directed_edges().unwrap().first().unwrap();
+        let destination = edge.destination_cell().unwrap();
+
+        // a second component, disconnected from the first one
+        let disconnected_origin = H3Cell::from_coordinate(Coord::from((24.3, 12.3)), res).unwrap();
+        let disconnected_edge = disconnected_origin
+            .directed_edges()
+            .unwrap()
+            .first()
+            .unwrap();
+        let disconnected_destination = disconnected_edge.destination_cell().unwrap();
+
+        let prepared_graph: PreparedH3EdgeGraph<_> = {
+            let mut graph = H3EdgeGraph::new(res);
+            graph.add_edge(edge, 5_u32).unwrap();
+            graph.add_edge(disconnected_edge, 7_u32).unwrap();
+            graph.try_into().unwrap()
+        };
+
+        let destinations = vec![destination, disconnected_destination];
+
+        let paths = prepared_graph
+            .shortest_path(
+                origin,
+                &destinations,
+                &DefaultShortestPathOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(paths.len(), 1);
+
+        let mut paths = prepared_graph
+            .shortest_path(origin, &destinations, &ReportUnreachableOptions {})
+            .unwrap();
+        paths.sort_unstable();
+        assert_eq!(paths.len(), 2);
+        assert!(paths[0].is_reachable());
+        assert_eq!(paths[0].destination_cell, destination);
+        assert_eq!(paths[0].cost, 5);
+        assert!(!paths[1].is_reachable());
+        assert_eq!(paths[1].origin_cell, origin);
+        assert_eq!(paths[1].destination_cell, disconnected_destination);
+        assert!(paths[1].is_empty());
+    }
+
+    struct AvoidCellsOptions {
+        avoid_cells: H3Treemap<H3Cell>,
+    }
+
+    impl ShortestPathOptions for AvoidCellsOptions {
+        fn avoid_cells(&self) -> Option<&H3Treemap<H3Cell>> {
+            Some(&self.avoid_cells)
+        }
+    }
+
+    #[test]
+    fn test_shortest_path_avoid_cells() {
+        let res = 8;
+        let origin = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), res).unwrap();
+        let destination = H3Cell::from_coordinate(Coord::from((23.4, 12.3)), res).unwrap();
+
+        let prepared_graph: PreparedH3EdgeGraph<_> = {
+            let mut graph = H3EdgeGraph::new(res);
+            graph
+                .add_path_using_cells(origin, destination, 20_u32)
+                .unwrap();
+            graph.try_into().unwrap()
+        };
+        let line_cells = grid_path_cells(origin, destination)
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+        assert!(line_cells.len() > 4);
+
+        let paths = prepared_graph
+            .shortest_path(
+                origin,
+                [destination],
+                &DefaultShortestPathOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(paths.len(), 1);
+
+        // avoiding the middle cells of the line leaves no way to the destination
+        let options = AvoidCellsOptions {
+            avoid_cells: line_cells[2..4].iter().copied().collect(),
+        };
+        let paths = prepared_graph
+            .shortest_path(origin, [destination], &options)
+            .unwrap();
+        assert!(paths.is_empty());
+
+        // avoiding cells not on the line does not change anything
+        let options = AvoidCellsOptions {
+            avoid_cells: origin
+                .grid_ring_unsafe(20)
+                .unwrap()
+                .iter()
+                .filter(|cell| !line_cells.contains(cell))
+                .collect(),
+        };
+        let paths = prepared_graph
+            .shortest_path(origin, [destination], &options)
+            .unwrap();
+        assert_eq!(paths.len(), 1);
+    }
+
+    #[test]
+    fn test_shortest_path_visit_order() {
+        let res = 8;
+        let origin = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), res).unwrap();
+
+        // a chain of three edges with increasing weights
+        let mut cells = vec![origin];
+        let prepared_graph: PreparedH3EdgeGraph<_> = {
+            let mut graph = H3EdgeGraph::new(res);
+            for weight in 1..=3_u32 {
+                let last_cell = *cells.last().unwrap();
+                let edge = last_cell
+                    .directed_edges()
+                    .unwrap()
+                    .iter()
+                    .find(|edge| !cells.contains(&edge.destination_cell().unwrap()))
+                    .unwrap();
+                graph.add_edge(edge, weight).unwrap();
+                cells.push(edge.destination_cell().unwrap());
+            }
+            graph.try_into().unwrap()
+        };
+
+        let mut visited = vec![];
+        let paths = prepared_graph
+            .shortest_path_visit(
+                origin,
+                vec![*cells.last().unwrap()],
+                &DefaultShortestPathOptions::default(),
+                |cell, weight| visited.push((cell, weight)),
+            )
+            .unwrap();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].cost, 6);
+
+        assert_eq!(visited.first(), Some(&(origin, 0)));
+        assert_eq!(
+            visited,
+            vec![(cells[0], 0), (cells[1], 1), (cells[2], 3), (cells[3], 6)]
+        );
+    }
 }
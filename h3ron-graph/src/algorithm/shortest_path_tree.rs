@@ -0,0 +1,104 @@
+use std::ops::Add;
+
+use num_traits::Zero;
+
+use h3ron::collections::H3CellMap;
+use h3ron::H3Cell;
+
+use crate::algorithm::dijkstra::edge_dijkstra_predecessors;
+use crate::error::Error;
+use crate::graph::GetCellEdges;
+
+/// Build the tree of the shortest paths from an origin cell to all reachable cells.
+pub trait ShortestPathTree<W> {
+    /// Find the shortest paths from `origin_cell` to all cells reachable within the
+    /// optional `weight_threshold`.
+    ///
+    /// Returns a map of all reached cells to a tuple of their predecessor cell on the
+    /// shortest path and the accumulated weight from the `origin_cell`. Following the
+    /// predecessors leads back to the `origin_cell`, which is its own predecessor.
+    fn shortest_path_tree(
+        &self,
+        origin_cell: H3Cell,
+        weight_threshold: Option<W>,
+    ) -> Result<H3CellMap<(H3Cell, W)>, Error>;
+}
+
+impl<W, G> ShortestPathTree<W> for G
+where
+    G: GetCellEdges<EdgeWeightType = W>,
+    W: Zero + Ord + Copy + Add,
+{
+    fn shortest_path_tree(
+        &self,
+        origin_cell: H3Cell,
+        weight_threshold: Option<W>,
+    ) -> Result<H3CellMap<(H3Cell, W)>, Error> {
+        let predecessors = edge_dijkstra_predecessors(self, &[origin_cell], weight_threshold)?;
+        Ok(predecessors
+            .iter()
+            .map(|(cell, (weight, predecessor_index))| {
+                let predecessor_cell = predecessors
+                    .get_index(*predecessor_index)
+                    .map_or(*cell, |(predecessor_cell, _)| *predecessor_cell);
+                (*cell, (predecessor_cell, *weight))
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use geo_types::{Geometry, Line};
+
+    use h3ron::iter::continuous_cells_to_edges;
+    use h3ron::{H3Cell, ToH3Cells};
+
+    use crate::algorithm::ShortestPathTree;
+    use crate::graph::{H3EdgeGraph, PreparedH3EdgeGraph};
+
+    #[test]
+    fn test_shortest_path_tree() {
+        let h3_resolution = 4;
+        let cell_sequence: Vec<_> = Geometry::Line(Line {
+            start: (10.0f64, 20.0f64).into(),
+            end: (20., 20.).into(),
+        })
+        .to_h3_cells(h3_resolution)
+        .unwrap()
+        .iter()
+        .collect();
+
+        let mut graph = H3EdgeGraph::new(h3_resolution);
+        for edge_result in continuous_cells_to_edges(&cell_sequence) {
+            graph.add_edge(edge_result.unwrap(), 10_u32).unwrap();
+        }
+        let prepared_graph: PreparedH3EdgeGraph<_> = graph.try_into().unwrap();
+
+        let origin_cell: H3Cell = cell_sequence[2];
+        let tree = prepared_graph
+            .shortest_path_tree(origin_cell, None)
+            .unwrap();
+        // the edges of the graph only lead along the direction of the line
+        assert_eq!(tree.len(), cell_sequence.len() - 2);
+        assert_eq!(tree[&origin_cell], (origin_cell, 0));
+
+        // follow the predecessors from the last cell back to the origin
+        let mut cell = *cell_sequence.last().unwrap();
+        let mut num_steps = 0;
+        while cell != origin_cell {
+            let (predecessor, weight) = tree[&cell];
+            assert_eq!(weight, tree[&predecessor].1 + 10);
+            cell = predecessor;
+            num_steps += 1;
+        }
+        assert_eq!(num_steps, cell_sequence.len() - 3);
+
+        let tree = prepared_graph
+            .shortest_path_tree(origin_cell, Some(30))
+            .unwrap();
+        assert_eq!(tree.len(), 4);
+    }
+}
@@ -21,6 +21,63 @@ pub trait WithinWeightThreshold<W> {
         origin_cell: H3Cell,
         weight_threshold: W,
     ) -> Result<H3CellMap<W>, Error>;
+
+    /// Find all cells connected to the graph within a given `weight_threshold` around
+    /// any of the given `origin_cells`.
+    ///
+    /// In contrast to [`WithinWeightThresholdMany::cells_within_weight_threshold_many`] this
+    /// runs a single search starting from all `origin_cells` at once. The returned weight of each
+    /// cell is the minimum weight to reach it from the nearest of the `origin_cells`.
+    ///
+    /// The provided implementation searches from each origin separately and merges the
+    /// results.
+    fn within_weight_threshold_multi_origin(
+        &self,
+        origin_cells: &[H3Cell],
+        weight_threshold: W,
+    ) -> Result<H3CellMap<W>, Error>
+    where
+        W: Ord + Copy,
+    {
+        let mut cells = H3CellMap::default();
+        for origin_cell in origin_cells {
+            for (cell, weight) in
+                self.cells_within_weight_threshold(*origin_cell, weight_threshold)?
+            {
+                match cells.entry(cell) {
+                    Entry::Occupied(mut occ) => {
+                        if weight < *occ.get() {
+                            occ.insert(weight);
+                        }
+                    }
+                    Entry::Vacant(vac) => {
+                        vac.insert(weight);
+                    }
+                }
+            }
+        }
+        Ok(cells)
+    }
+
+    /// Sum the values returned by `value_fn` over all cells connected to the graph within
+    /// a given `weight_threshold` around the given `origin_cell`.
+    ///
+    /// This can be used - for example - to calculate the population reachable from the `origin_cell`.
+    fn within_weight_threshold_accumulate<F>(
+        &self,
+        origin_cell: H3Cell,
+        weight_threshold: W,
+        value_fn: F,
+    ) -> Result<f64, Error>
+    where
+        F: Fn(&H3Cell) -> f64,
+    {
+        Ok(self
+            .cells_within_weight_threshold(origin_cell, weight_threshold)?
+            .keys()
+            .map(value_fn)
+            .sum())
+    }
 }
 
 impl<W, G> WithinWeightThreshold<W> for G
@@ -33,7 +90,15 @@ where
         origin_cell: H3Cell,
         weight_threshold: W,
     ) -> Result<H3CellMap<W>, Error> {
-        edge_dijkstra_weight_threshold(self, &origin_cell, weight_threshold)
+        edge_dijkstra_weight_threshold(self, &[origin_cell], weight_threshold)
+    }
+
+    fn within_weight_threshold_multi_origin(
+        &self,
+        origin_cells: &[H3Cell],
+        weight_threshold: W,
+    ) -> Result<H3CellMap<W>, Error> {
+        edge_dijkstra_weight_threshold(self, origin_cells, weight_threshold)
     }
 }
 
@@ -107,10 +172,12 @@ mod tests {
 
     use geo_types::{Geometry, Line};
 
+    use h3ron::collections::H3CellMap;
     use h3ron::iter::continuous_cells_to_edges;
     use h3ron::{H3Cell, ToH3Cells};
 
     use crate::algorithm::{WithinWeightThreshold, WithinWeightThresholdMany};
+    use crate::error::Error;
     use crate::graph::{GetStats, H3EdgeGraph, PreparedH3EdgeGraph};
 
     /// a simple graph consisting of a single line
@@ -147,6 +214,83 @@ mod tests {
         assert!(weights.contains(&30));
     }
 
+    #[test]
+    fn test_within_weight_threshold_accumulate() {
+        let (cell_sequence, prepared_graph) = line_graph(10);
+        let num_cells = prepared_graph
+            .cells_within_weight_threshold(cell_sequence[3], 30)
+            .unwrap()
+            .len();
+        assert_eq!(num_cells, 4);
+
+        let accumulated = prepared_graph
+            .within_weight_threshold_accumulate(cell_sequence[3], 30, |_| 2.5)
+            .unwrap();
+        assert!((accumulated - num_cells as f64 * 2.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_within_weight_threshold_multi_origin() {
+        let (cell_sequence, prepared_graph) = line_graph(10);
+        let within_threshold = prepared_graph
+            .within_weight_threshold_multi_origin(&[cell_sequence[0], cell_sequence[10]], 30)
+            .unwrap();
+
+        // cells near the first origin
+        assert_eq!(within_threshold[&cell_sequence[0]], 0);
+        assert_eq!(within_threshold[&cell_sequence[2]], 20);
+        assert_eq!(within_threshold[&cell_sequence[3]], 30);
+        assert!(!within_threshold.contains_key(&cell_sequence[4]));
+
+        // cells near the second origin
+        assert_eq!(within_threshold[&cell_sequence[10]], 0);
+        assert_eq!(within_threshold[&cell_sequence[11]], 10);
+        assert_eq!(within_threshold[&cell_sequence[13]], 30);
+        assert!(!within_threshold.contains_key(&cell_sequence[14]));
+
+        // same as aggregating the single-origin searches using the minimum
+        let many = prepared_graph
+            .cells_within_weight_threshold_many(
+                vec![cell_sequence[0], cell_sequence[10]],
+                30,
+                |existing, new| {
+                    if new < *existing {
+                        *existing = new
+                    }
+                },
+            )
+            .unwrap();
+        assert_eq!(within_threshold, many);
+    }
+
+    /// implements only the required methods of [`WithinWeightThreshold`]
+    struct RequiredMethodsOnly(PreparedH3EdgeGraph<u32>);
+
+    impl WithinWeightThreshold<u32> for RequiredMethodsOnly {
+        fn cells_within_weight_threshold(
+            &self,
+            origin_cell: H3Cell,
+            weight_threshold: u32,
+        ) -> Result<H3CellMap<u32>, Error> {
+            self.0
+                .cells_within_weight_threshold(origin_cell, weight_threshold)
+        }
+    }
+
+    #[test]
+    fn test_within_weight_threshold_multi_origin_provided() {
+        let (cell_sequence, prepared_graph) = line_graph(10);
+        let origin_cells = [cell_sequence[0], cell_sequence[5]];
+        let expected = prepared_graph
+            .within_weight_threshold_multi_origin(&origin_cells, 30)
+            .unwrap();
+
+        let provided = RequiredMethodsOnly(prepared_graph)
+            .within_weight_threshold_multi_origin(&origin_cells, 30)
+            .unwrap();
+        assert_eq!(provided, expected);
+    }
+
     #[test]
     fn test_cells_within_weight_threshold_many() {
         let (cell_sequence, prepared_graph) = line_graph(10);
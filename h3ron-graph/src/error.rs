@@ -14,6 +14,9 @@ pub enum Error {
     #[error("too high h3 resolution: {0}")]
     TooHighH3Resolution(u8),
 
+    #[error("too low h3 resolution: {0}")]
+    TooLowH3Resolution(u8),
+
     #[error("empty path")]
     EmptyPath,
 
@@ -29,6 +32,9 @@ pub enum Error {
     #[error("path is segmented into multiple parts")]
     SegmentedPath,
 
+    #[error("unsupported graph serialization version: {0}")]
+    UnsupportedGraphVersion(u16),
+
     #[error("unknown error: {0}")]
     UnknownWithMessage(String),
 }
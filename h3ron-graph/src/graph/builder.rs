@@ -0,0 +1,71 @@
+use std::ops::Add;
+
+use h3ron::collections::H3Treemap;
+use h3ron::{H3Cell, Index};
+
+use crate::error::Error;
+use crate::graph::H3EdgeGraph;
+
+/// Build a graph connecting each cell of `cells` to all of its grid neighbors which are
+/// contained in `cells` as well.
+///
+/// All edges are created in both directions and get the same `weight`. All cells
+/// must be at `h3_resolution`.
+pub fn from_cell_set<W>(
+    cells: &H3Treemap<H3Cell>,
+    h3_resolution: u8,
+    weight: W,
+) -> Result<H3EdgeGraph<W>, Error>
+where
+    W: PartialOrd + PartialEq + Add + Copy,
+{
+    let mut graph = H3EdgeGraph::new(h3_resolution);
+    for cell in cells.iter() {
+        if cell.resolution() != h3_resolution {
+            return Err(Error::MixedH3Resolutions(h3_resolution, cell.resolution()));
+        }
+        for edge in cell.directed_edges()?.iter() {
+            if cells.contains(&edge.destination_cell()?) {
+                graph.add_edge(edge, weight)?;
+            }
+        }
+    }
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::Coord;
+
+    use h3ron::collections::H3Treemap;
+    use h3ron::H3Cell;
+
+    use super::from_cell_set;
+
+    #[test]
+    fn test_from_cell_set() {
+        let cells: H3Treemap<_> = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), 8)
+            .unwrap()
+            .grid_disk(1)
+            .unwrap()
+            .iter()
+            .collect();
+
+        let graph = from_cell_set(&cells, 8, 1_u32).unwrap();
+        // 6 bidirectional edges from the center to the ring and 6 bidirectional edges
+        // between the neighboring cells within the ring.
+        assert_eq!(graph.num_edges(), 24);
+        assert_eq!(graph.num_nodes().unwrap(), 7);
+    }
+
+    #[test]
+    fn test_from_cell_set_wrong_resolution() {
+        let cells: H3Treemap<_> = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), 8)
+            .unwrap()
+            .grid_disk(1)
+            .unwrap()
+            .iter()
+            .collect();
+        assert!(from_cell_set(&cells, 7, 1_u32).is_err());
+    }
+}
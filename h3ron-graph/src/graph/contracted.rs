@@ -0,0 +1,545 @@
+//! Contraction hierarchies for fast point-to-point queries on static graphs.
+//!
+//! The preprocessing contracts the nodes of the graph one after another and inserts
+//! shortcut edges to preserve the shortest paths between the remaining nodes. Queries only need to
+//! follow edges leading to higher ranked nodes, which results in a much smaller search space
+//! compared to a plain Dijkstra search.
+//!
+//! See [Contraction hierarchies](https://en.wikipedia.org/wiki/Contraction_hierarchies).
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::ops::Add;
+
+use num_traits::Zero;
+use serde::{Deserialize, Serialize};
+
+use h3ron::collections::hashbrown::hash_map::Entry;
+use h3ron::collections::{H3CellMap, HashMap};
+use h3ron::{H3Cell, HasH3Resolution};
+
+use crate::algorithm::path::{DirectedEdgePath, Path};
+use crate::error::Error;
+use crate::graph::H3EdgeGraph;
+
+/// Maximum number of nodes settled during a witness search. Reaching this limit
+/// only causes unnecessary shortcuts to be added, the correctness of the queries is not affected.
+const WITNESS_SEARCH_SETTLE_LIMIT: usize = 500;
+
+#[derive(Serialize, Deserialize, Clone)]
+struct ContractedEdge<W> {
+    /// the node at the other end of the edge
+    node: usize,
+    weight: W,
+
+    /// the contracted node bridged by this edge in case the edge is a shortcut
+    via: Option<usize>,
+}
+
+/// adjacent nodes with the weight of the connecting edge and the bridged node of shortcuts.
+type Adjacency<W> = HashMap<usize, (W, Option<usize>)>;
+
+/// A graph preprocessed using contraction hierarchies for fast point-to-point queries.
+///
+/// The preprocessing is expensive, so this is only useful for graphs which do not change and
+/// are used for many queries. Changing the graph requires creating a new `ContractedGraph`.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ContractedGraph<W> {
+    h3_resolution: u8,
+    cells: Vec<H3Cell>,
+    cell_nodes: H3CellMap<usize>,
+    ranks: Vec<usize>,
+
+    /// edges leading from a node to higher ranked nodes, keyed by the origin node.
+    upward_edges: Vec<Vec<ContractedEdge<W>>>,
+
+    /// edges leading from higher ranked nodes to a node, keyed by the destination node.
+    downward_edges: Vec<Vec<ContractedEdge<W>>>,
+
+    num_shortcuts: usize,
+}
+
+impl<W> ContractedGraph<W> {
+    pub fn num_nodes(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// number of shortcut edges added during the contraction
+    pub fn num_shortcuts(&self) -> usize {
+        self.num_shortcuts
+    }
+}
+
+impl<W> ContractedGraph<W>
+where
+    W: PartialOrd + PartialEq + Add + Copy + Ord + Zero,
+{
+    pub fn from_h3edge_graph(graph: &H3EdgeGraph<W>) -> Result<Self, Error> {
+        let mut cells = Vec::new();
+        let mut cell_nodes = H3CellMap::default();
+        let mut outgoing: Vec<Adjacency<W>> = Vec::new();
+        let mut incoming: Vec<Adjacency<W>> = Vec::new();
+
+        let mut node_of_cell = |cell: H3Cell| -> usize {
+            *cell_nodes.entry(cell).or_insert_with(|| {
+                cells.push(cell);
+                outgoing.push(Adjacency::default());
+                incoming.push(Adjacency::default());
+                cells.len() - 1
+            })
+        };
+
+        let mut edges = Vec::with_capacity(graph.num_edges());
+        for (edge, weight) in graph.iter_edges() {
+            let edge_cells = edge.cells()?;
+            edges.push((
+                node_of_cell(edge_cells.origin),
+                node_of_cell(edge_cells.destination),
+                *weight,
+            ));
+        }
+        for (origin, destination, weight) in edges {
+            outgoing[origin].insert(destination, (weight, None));
+            incoming[destination].insert(origin, (weight, None));
+        }
+
+        let num_nodes = cells.len();
+        log::debug!(
+            "contracting graph with {} nodes @ r={}",
+            num_nodes,
+            graph.h3_resolution
+        );
+
+        let mut ranks = vec![0; num_nodes];
+        let mut upward_edges = Vec::with_capacity(num_nodes);
+        upward_edges.resize_with(num_nodes, Vec::new);
+        let mut downward_edges = Vec::with_capacity(num_nodes);
+        downward_edges.resize_with(num_nodes, Vec::new);
+        let mut num_contracted_neighbors = vec![0_usize; num_nodes];
+        let mut num_shortcuts = 0;
+
+        let priority = |node: usize,
+                        shortcuts: &[(usize, usize, W)],
+                        outgoing: &[Adjacency<W>],
+                        incoming: &[Adjacency<W>],
+                        num_contracted_neighbors: &[usize]| {
+            // edge difference, extended by the number of contracted neighbors to
+            // contract the graph uniformly.
+            shortcuts.len() as i64 - (outgoing[node].len() + incoming[node].len()) as i64
+                + num_contracted_neighbors[node] as i64
+        };
+
+        let mut queue = BinaryHeap::with_capacity(num_nodes);
+        for node in 0..num_nodes {
+            let shortcuts = find_shortcuts(&outgoing, &incoming, node);
+            queue.push(Reverse((
+                priority(
+                    node,
+                    &shortcuts,
+                    &outgoing,
+                    &incoming,
+                    &num_contracted_neighbors,
+                ),
+                node,
+            )));
+        }
+
+        let mut next_rank = 0;
+        while let Some(Reverse((_, node))) = queue.pop() {
+            // lazy update: the priority may have changed since the node was queued.
+            let shortcuts = find_shortcuts(&outgoing, &incoming, node);
+            let current_priority = priority(
+                node,
+                &shortcuts,
+                &outgoing,
+                &incoming,
+                &num_contracted_neighbors,
+            );
+            if let Some(Reverse((next_priority, _))) = queue.peek() {
+                if current_priority > *next_priority {
+                    queue.push(Reverse((current_priority, node)));
+                    continue;
+                }
+            }
+
+            // all remaining neighbors will be contracted later, so they have a higher rank.
+            for (neighbor, (weight, via)) in outgoing[node].drain() {
+                incoming[neighbor].remove(&node);
+                num_contracted_neighbors[neighbor] += 1;
+                upward_edges[node].push(ContractedEdge {
+                    node: neighbor,
+                    weight,
+                    via,
+                });
+            }
+            for (neighbor, (weight, via)) in incoming[node].drain() {
+                outgoing[neighbor].remove(&node);
+                num_contracted_neighbors[neighbor] += 1;
+                downward_edges[node].push(ContractedEdge {
+                    node: neighbor,
+                    weight,
+                    via,
+                });
+            }
+
+            for (origin, destination, weight) in shortcuts {
+                let is_improvement = match outgoing[origin].entry(destination) {
+                    Entry::Occupied(mut occ) => {
+                        if weight < occ.get().0 {
+                            occ.insert((weight, Some(node)));
+                            true
+                        } else {
+                            false
+                        }
+                    }
+                    Entry::Vacant(vac) => {
+                        vac.insert((weight, Some(node)));
+                        true
+                    }
+                };
+                if is_improvement {
+                    incoming[destination].insert(origin, (weight, Some(node)));
+                    num_shortcuts += 1;
+                }
+            }
+
+            ranks[node] = next_rank;
+            next_rank += 1;
+        }
+
+        log::debug!("contraction added {} shortcuts", num_shortcuts);
+
+        Ok(Self {
+            h3_resolution: graph.h3_resolution,
+            cells,
+            cell_nodes,
+            ranks,
+            upward_edges,
+            downward_edges,
+            num_shortcuts,
+        })
+    }
+
+    /// Find the shortest path between `origin_cell` and `destination_cell`.
+    ///
+    /// Returns `None` when one of the cells is not part of the graph or when there is no
+    /// path connecting both cells.
+    pub fn bidirectional_ch_query(
+        &self,
+        origin_cell: H3Cell,
+        destination_cell: H3Cell,
+    ) -> Result<Option<Path<W>>, Error> {
+        let (origin, destination) = match (
+            self.cell_nodes.get(&origin_cell),
+            self.cell_nodes.get(&destination_cell),
+        ) {
+            (Some(origin), Some(destination)) => (*origin, *destination),
+            _ => return Ok(None),
+        };
+        if origin == destination {
+            return Ok(Some(
+                (
+                    DirectedEdgePath::OriginIsDestination(origin_cell),
+                    W::zero(),
+                )
+                    .try_into()?,
+            ));
+        }
+
+        let forward = upward_search(&self.upward_edges, origin);
+        let backward = upward_search(&self.downward_edges, destination);
+
+        let mut best: Option<(W, usize)> = None;
+        for (node, (forward_weight, _)) in forward.iter() {
+            if let Some((backward_weight, _)) = backward.get(node) {
+                let weight = *forward_weight + *backward_weight;
+                let is_better = match best {
+                    Some((best_weight, _)) => weight < best_weight,
+                    None => true,
+                };
+                if is_better {
+                    best = Some((weight, *node));
+                }
+            }
+        }
+        let (cost, meeting_node) = match best {
+            Some(best) => best,
+            None => return Ok(None),
+        };
+
+        // sequence of nodes from the origin to the destination, possibly connected by shortcuts
+        let mut nodes = vec![meeting_node];
+        let mut node = meeting_node;
+        while node != origin {
+            node = forward[&node].1;
+            nodes.push(node);
+        }
+        nodes.reverse();
+        node = meeting_node;
+        while node != destination {
+            node = backward[&node].1;
+            nodes.push(node);
+        }
+
+        let mut h3edges = Vec::with_capacity(nodes.len());
+        for window in nodes.windows(2) {
+            self.unpack_edge(window[0], window[1], &mut h3edges)?;
+        }
+        Ok(Some(
+            (DirectedEdgePath::DirectedEdgeSequence(h3edges), cost).try_into()?,
+        ))
+    }
+
+    /// resolve the - possible shortcut - edge between `origin` and `destination` to the
+    /// directed edges of the original graph.
+    fn unpack_edge(
+        &self,
+        origin: usize,
+        destination: usize,
+        h3edges: &mut Vec<h3ron::H3DirectedEdge>,
+    ) -> Result<(), Error> {
+        let mut stack = vec![(origin, destination)];
+        while let Some((origin, destination)) = stack.pop() {
+            let edge = if self.ranks[destination] > self.ranks[origin] {
+                self.upward_edges[origin]
+                    .iter()
+                    .find(|edge| edge.node == destination)
+            } else {
+                self.downward_edges[destination]
+                    .iter()
+                    .find(|edge| edge.node == origin)
+            }
+            .ok_or_else(|| Error::Other("edge missing in contracted graph".to_string()))?;
+
+            if let Some(via) = edge.via {
+                // the first half gets processed first
+                stack.push((via, destination));
+                stack.push((origin, via));
+            } else {
+                h3edges.push(self.cells[origin].directed_edge_to(self.cells[destination])?);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W> HasH3Resolution for ContractedGraph<W> {
+    fn h3_resolution(&self) -> u8 {
+        self.h3_resolution
+    }
+}
+
+impl<W> TryFrom<&H3EdgeGraph<W>> for ContractedGraph<W>
+where
+    W: PartialOrd + PartialEq + Add + Copy + Ord + Zero,
+{
+    type Error = Error;
+
+    fn try_from(graph: &H3EdgeGraph<W>) -> Result<Self, Self::Error> {
+        Self::from_h3edge_graph(graph)
+    }
+}
+
+/// find the shortcuts required to preserve the shortest paths when `node` gets contracted.
+fn find_shortcuts<W>(
+    outgoing: &[Adjacency<W>],
+    incoming: &[Adjacency<W>],
+    node: usize,
+) -> Vec<(usize, usize, W)>
+where
+    W: Ord + Copy + Zero,
+{
+    let mut shortcuts = vec![];
+    let max_outgoing_weight = match outgoing[node].values().map(|(weight, _)| *weight).max() {
+        Some(weight) => weight,
+        None => return shortcuts,
+    };
+
+    for (origin, (incoming_weight, _)) in incoming[node].iter() {
+        let witnesses = witness_search(
+            outgoing,
+            *origin,
+            node,
+            *incoming_weight + max_outgoing_weight,
+        );
+        for (destination, (outgoing_weight, _)) in outgoing[node].iter() {
+            if destination == origin {
+                continue;
+            }
+            let weight = *incoming_weight + *outgoing_weight;
+            match witnesses.get(destination) {
+                Some(witness_weight) if *witness_weight <= weight => {}
+                _ => shortcuts.push((*origin, *destination, weight)),
+            }
+        }
+    }
+    shortcuts
+}
+
+/// Dijkstra search from `origin` not passing through `excluded`.
+///
+/// All returned weights are weights of existing paths, though not necessarily the shortest ones
+/// as the search is limited.
+fn witness_search<W>(
+    outgoing: &[Adjacency<W>],
+    origin: usize,
+    excluded: usize,
+    weight_limit: W,
+) -> HashMap<usize, W>
+where
+    W: Ord + Copy + Zero,
+{
+    let mut weights = HashMap::default();
+    let mut queue = BinaryHeap::new();
+    weights.insert(origin, W::zero());
+    queue.push(Reverse((W::zero(), origin)));
+
+    let mut num_settled = 0;
+    while let Some(Reverse((weight, node))) = queue.pop() {
+        if weight > weight_limit || num_settled >= WITNESS_SEARCH_SETTLE_LIMIT {
+            break;
+        }
+        if weights.get(&node).is_some_and(|w| weight > *w) {
+            continue;
+        }
+        num_settled += 1;
+
+        for (neighbor, (edge_weight, _)) in outgoing[node].iter() {
+            if *neighbor == excluded {
+                continue;
+            }
+            let new_weight = weight + *edge_weight;
+            match weights.entry(*neighbor) {
+                Entry::Occupied(mut occ) => {
+                    if new_weight < *occ.get() {
+                        occ.insert(new_weight);
+                        queue.push(Reverse((new_weight, *neighbor)));
+                    }
+                }
+                Entry::Vacant(vac) => {
+                    vac.insert(new_weight);
+                    queue.push(Reverse((new_weight, *neighbor)));
+                }
+            }
+        }
+    }
+    weights
+}
+
+/// Dijkstra search following only the edges to higher ranked nodes.
+///
+/// Returns the settled nodes with their weight and the preceding node.
+fn upward_search<W>(edges: &[Vec<ContractedEdge<W>>], start: usize) -> HashMap<usize, (W, usize)>
+where
+    W: Ord + Copy + Zero,
+{
+    let mut settled: HashMap<usize, (W, usize)> = HashMap::default();
+    let mut tentative: HashMap<usize, (W, usize)> = HashMap::default();
+    let mut queue = BinaryHeap::new();
+    tentative.insert(start, (W::zero(), start));
+    queue.push(Reverse((W::zero(), start)));
+
+    while let Some(Reverse((weight, node))) = queue.pop() {
+        if settled.contains_key(&node) {
+            continue;
+        }
+        settled.insert(node, tentative[&node]);
+
+        for edge in edges[node].iter() {
+            if settled.contains_key(&edge.node) {
+                continue;
+            }
+            let new_weight = weight + edge.weight;
+            match tentative.entry(edge.node) {
+                Entry::Occupied(mut occ) => {
+                    if new_weight < occ.get().0 {
+                        occ.insert((new_weight, node));
+                        queue.push(Reverse((new_weight, edge.node)));
+                    }
+                }
+                Entry::Vacant(vac) => {
+                    vac.insert((new_weight, node));
+                    queue.push(Reverse((new_weight, edge.node)));
+                }
+            }
+        }
+    }
+    settled
+}
+
+#[cfg(test)]
+mod tests {
+    use h3ron::{H3Cell, Index};
+
+    use crate::algorithm::shortest_path::DefaultShortestPathOptions;
+    use crate::algorithm::ShortestPath;
+    use crate::graph::{ContractedGraph, H3EdgeGraph, PreparedH3EdgeGraph};
+
+    /// a grid graph with varying weights
+    fn grid_graph() -> (Vec<H3Cell>, H3EdgeGraph<u32>) {
+        let center = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
+        let cells: Vec<_> = center.grid_disk(6).unwrap().into();
+
+        let mut graph = H3EdgeGraph::new(center.resolution());
+        for cell in cells.iter() {
+            for neighbor in cell.grid_ring_unsafe(1).unwrap().iter() {
+                if !cells.contains(&neighbor) {
+                    continue;
+                }
+                let weight = ((cell.h3index() ^ neighbor.h3index()) % 7 + 1) as u32;
+                graph.add_edge_using_cells(*cell, neighbor, weight).unwrap();
+            }
+        }
+        (cells, graph)
+    }
+
+    #[test]
+    fn ch_query_matches_dijkstra() {
+        let (cells, graph) = grid_graph();
+        let contracted_graph = ContractedGraph::from_h3edge_graph(&graph).unwrap();
+        assert_eq!(contracted_graph.num_nodes(), cells.len());
+
+        let prepared_graph: PreparedH3EdgeGraph<_> = graph.clone().try_into().unwrap();
+        let options = DefaultShortestPathOptions::default();
+
+        for origin_cell in cells.iter().step_by(13) {
+            for destination_cell in cells.iter().step_by(7) {
+                let dijkstra_paths = prepared_graph
+                    .shortest_path(*origin_cell, [*destination_cell], &options)
+                    .unwrap();
+                assert_eq!(dijkstra_paths.len(), 1);
+
+                let ch_path = contracted_graph
+                    .bidirectional_ch_query(*origin_cell, *destination_cell)
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(ch_path.cost, dijkstra_paths[0].cost);
+                assert_eq!(ch_path.origin_cell, *origin_cell);
+                assert_eq!(ch_path.destination_cell, *destination_cell);
+
+                // the unpacked path consists of consecutive edges of the original graph
+                let mut cost = 0;
+                for window in ch_path.directed_edge_path.edges().windows(2) {
+                    assert_eq!(
+                        window[0].destination_cell().unwrap(),
+                        window[1].origin_cell().unwrap()
+                    );
+                }
+                for edge in ch_path.directed_edge_path.edges() {
+                    cost += graph.edge_weight(edge).unwrap();
+                }
+                assert_eq!(cost, ch_path.cost);
+            }
+        }
+    }
+
+    #[test]
+    fn ch_query_unknown_cell() {
+        let (cells, graph) = grid_graph();
+        let contracted_graph = ContractedGraph::from_h3edge_graph(&graph).unwrap();
+        let outside_cell = cells[0].grid_ring_unsafe(20).unwrap().first().unwrap();
+        assert!(contracted_graph
+            .bidirectional_ch_query(cells[0], outside_cell)
+            .unwrap()
+            .is_none());
+    }
+}
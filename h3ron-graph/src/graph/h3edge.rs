@@ -1,16 +1,21 @@
-use std::ops::Add;
+use std::ops::{Add, Div, Mul, Sub};
+use std::sync::OnceLock;
 
-use geo_types::MultiPolygon;
+use geo_types::{MultiLineString, MultiPolygon};
+use num_traits::{FromPrimitive, ToPrimitive, Zero};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::algorithm::covered_area::{cells_covered_area, CoveredArea};
+use crate::algorithm::within_weight_threshold::WithinWeightThreshold;
 use h3ron::collections::hashbrown::hash_map::Entry;
-use h3ron::collections::{H3CellMap, H3EdgeMap, RandomState};
-use h3ron::{H3Cell, H3DirectedEdge, HasH3Resolution};
+use h3ron::collections::{H3CellMap, H3EdgeMap, H3Treemap, RandomState};
+use h3ron::to_geo::ToLineString;
+use h3ron::{grid_path_cells, H3Cell, H3DirectedEdge, HasH3Resolution};
 
 use crate::error::Error;
 use crate::graph::node::NodeType;
-use crate::graph::{EdgeWeight, GetEdge, GetStats};
+use crate::graph::{EdgeWeight, GetCellEdges, GetEdge, GetStats};
 
 use super::GraphStats;
 
@@ -18,6 +23,18 @@ use super::GraphStats;
 pub struct H3EdgeGraph<W> {
     pub edges: H3EdgeMap<W>,
     pub h3_resolution: u8,
+
+    /// memoized result of [`H3EdgeGraph::node_count_cached`] as a tuple of
+    /// `(num_edges, node_count)`.
+    #[serde(skip)]
+    node_count: OnceLock<(usize, usize)>,
+}
+
+impl<W> H3EdgeGraph<W> {
+    /// Discard the node count memoized by [`H3EdgeGraph::node_count_cached`].
+    pub fn invalidate_node_count_cache(&mut self) {
+        self.node_count.take();
+    }
 }
 
 impl<W> H3EdgeGraph<W>
@@ -28,6 +45,7 @@ where
         Self {
             h3_resolution,
             edges: Default::default(),
+            node_count: OnceLock::new(),
         }
     }
 
@@ -38,6 +56,26 @@ where
         Ok(self.nodes()?.len())
     }
 
+    /// The same as [`H3EdgeGraph::num_nodes`], but the node count is memoized for
+    /// subsequent calls.
+    ///
+    /// The memoized value gets discarded when edges are added or removed using the methods
+    /// of the graph. Modifications of `edges` changing the number of edges are detected as
+    /// well. After other direct modifications of `edges` - like replacing an edge -
+    /// [`H3EdgeGraph::invalidate_node_count_cache`] must be called.
+    pub fn node_count_cached(&self) -> Result<usize, Error> {
+        let num_edges = self.edges.len();
+        if let Some((cached_num_edges, node_count)) = self.node_count.get() {
+            if *cached_num_edges == num_edges {
+                return Ok(*node_count);
+            }
+            // outdated, the memoized value can only be replaced using a mutable reference.
+            return self.num_nodes();
+        }
+        let node_count = self.num_nodes()?;
+        Ok(self.node_count.get_or_init(|| (num_edges, node_count)).1)
+    }
+
     pub fn num_edges(&self) -> usize {
         self.edges.len()
     }
@@ -103,6 +141,7 @@ where
             }
             Entry::Vacant(vac) => {
                 vac.insert(weight);
+                self.node_count.take();
             }
         }
         Ok(())
@@ -136,6 +175,424 @@ where
     pub fn iter_edges(&self) -> impl Iterator<Item = (H3DirectedEdge, &W)> {
         self.edges.iter().map(|(edge, weight)| (*edge, weight))
     }
+
+    /// Extract the part of the graph consisting of the edges with both, the origin- and the
+    /// destination cell contained in `cells`.
+    pub fn subgraph(&self, cells: &H3Treemap<H3Cell>) -> Result<Self, Error> {
+        let mut subgraph = Self::new(self.h3_resolution);
+        for (edge, weight) in self.edges.iter() {
+            if cells.contains(&edge.origin_cell()?) && cells.contains(&edge.destination_cell()?) {
+                subgraph.edges.insert(*edge, *weight);
+            }
+        }
+        Ok(subgraph)
+    }
+
+    /// Build a `MultiLineString` containing a `LineString` for each edge whose weight
+    /// passes the `predicate`.
+    ///
+    /// The edges are not merged into longer linestrings, so the number of linestrings
+    /// equals the number of edges passing the `predicate`.
+    pub fn to_multilinestring_filtered<F>(
+        &self,
+        predicate: F,
+    ) -> Result<MultiLineString<f64>, Error>
+    where
+        F: Fn(&W) -> bool,
+    {
+        Ok(MultiLineString::new(
+            self.edges
+                .iter()
+                .filter(|(_, weight)| predicate(weight))
+                .map(|(edge, _)| edge.to_linestring())
+                .collect::<Result<Vec<_>, _>>()?,
+        ))
+    }
+
+    /// Export the graph to a [`petgraph::Graph`] to be able to use the algorithms
+    /// provided by `petgraph`.
+    ///
+    /// Returns the created graph and a map to look up the node index of each cell.
+    ///
+    /// Requires the `petgraph` feature.
+    #[cfg(feature = "petgraph")]
+    pub fn to_petgraph(
+        &self,
+    ) -> Result<
+        (
+            petgraph::Graph<H3Cell, W>,
+            H3CellMap<petgraph::graph::NodeIndex>,
+        ),
+        Error,
+    > {
+        let nodes = self.nodes()?;
+        let mut graph = petgraph::Graph::with_capacity(nodes.len(), self.edges.len());
+        let node_indexes: H3CellMap<_> = nodes
+            .keys()
+            .map(|cell| (*cell, graph.add_node(*cell)))
+            .collect();
+        for (edge, weight) in self.edges.iter() {
+            let cells = edge.cells()?;
+            graph.add_edge(
+                node_indexes[&cells.origin],
+                node_indexes[&cells.destination],
+                *weight,
+            );
+        }
+        Ok((graph, node_indexes))
+    }
+
+    /// Create a graph weighted by the travel time in seconds to traverse the edges
+    /// at the constant speed of `speed_m_per_s`.
+    ///
+    /// The current weights are discarded. The travel distance of an edge is the distance
+    /// between the centroids of its origin and destination cells.
+    pub fn to_travel_time(&self, speed_m_per_s: f64) -> Result<H3EdgeGraph<f64>, Error> {
+        if !(speed_m_per_s.is_finite() && speed_m_per_s > 0.0) {
+            return Err(Error::Other(format!(
+                "speed must be a positive number, found {}",
+                speed_m_per_s
+            )));
+        }
+        let mut travel_time_graph = H3EdgeGraph::new(self.h3_resolution);
+        for edge in self.edges.keys() {
+            travel_time_graph.add_edge(*edge, edge.cell_centroid_distance_m()? / speed_m_per_s)?;
+        }
+        Ok(travel_time_graph)
+    }
+}
+
+/// The differences between two [`H3EdgeGraph`]s as returned by [`H3EdgeGraph::diff`].
+#[derive(Clone, Debug)]
+pub struct GraphDiff<W> {
+    /// edges only contained in the other graph, with their weights
+    pub added: H3EdgeMap<W>,
+
+    /// edges only contained in the graph `diff` was called on, with their weights
+    pub removed: H3EdgeMap<W>,
+
+    /// edges contained in both graphs with differing weights as tuples of
+    /// `(old_weight, new_weight)`
+    pub changed: H3EdgeMap<(W, W)>,
+}
+
+impl<W> GraphDiff<W> {
+    /// the graphs do not differ
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl<W> H3EdgeGraph<W>
+where
+    W: PartialEq + Copy,
+{
+    /// Determine the edges which have been added, removed or which have
+    /// changed weights in `other` compared to `self`.
+    pub fn diff(&self, other: &Self) -> GraphDiff<W> {
+        let mut graph_diff = GraphDiff {
+            added: H3EdgeMap::default(),
+            removed: H3EdgeMap::default(),
+            changed: H3EdgeMap::default(),
+        };
+        for (edge, weight) in self.edges.iter() {
+            match other.edges.get(edge) {
+                Some(other_weight) => {
+                    if weight != other_weight {
+                        graph_diff.changed.insert(*edge, (*weight, *other_weight));
+                    }
+                }
+                None => {
+                    graph_diff.removed.insert(*edge, *weight);
+                }
+            }
+        }
+        for (edge, weight) in other.edges.iter() {
+            if !self.edges.contains_key(edge) {
+                graph_diff.added.insert(*edge, *weight);
+            }
+        }
+        graph_diff
+    }
+}
+
+impl<W> H3EdgeGraph<W>
+where
+    W: Ord + Copy,
+{
+    /// Restrict the weights of all edges to the range `min..=max`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min > max`.
+    pub fn clamp_weights(&mut self, min: W, max: W) {
+        for weight in self.edges.values_mut() {
+            *weight = (*weight).clamp(min, max);
+        }
+    }
+
+    /// Remove all edges with weights outside of the range spanned by the `lower_quantile`
+    /// and `upper_quantile` of the weights of all edges.
+    ///
+    /// Both quantiles must be in the range `0.0..=1.0`, with `lower_quantile` not
+    /// being larger than `upper_quantile`.
+    pub fn remove_weight_outliers(
+        &mut self,
+        lower_quantile: f64,
+        upper_quantile: f64,
+    ) -> Result<(), Error> {
+        if !((0.0..=1.0).contains(&lower_quantile)
+            && (0.0..=1.0).contains(&upper_quantile)
+            && lower_quantile <= upper_quantile)
+        {
+            return Err(Error::Other(format!(
+                "invalid quantiles {} and {}",
+                lower_quantile, upper_quantile
+            )));
+        }
+        if self.edges.is_empty() {
+            return Ok(());
+        }
+
+        let mut weights: Vec<W> = self.edges.values().copied().collect();
+        weights.sort_unstable();
+        let quantile_weight =
+            |quantile: f64| weights[(quantile * (weights.len() - 1) as f64).round() as usize];
+        let lower = quantile_weight(lower_quantile);
+        let upper = quantile_weight(upper_quantile);
+
+        self.edges
+            .retain(|_, weight| *weight >= lower && *weight <= upper);
+        self.invalidate_node_count_cache();
+        Ok(())
+    }
+}
+
+impl<W> H3EdgeGraph<W>
+where
+    W: Ord + Add + Copy,
+{
+    /// Build a minimum spanning tree connecting all nodes of the graph using the edges
+    /// with the lowest weights.
+    ///
+    /// The direction of the edges is ignored, so the spanning tree contains each connection
+    /// between two cells at most once using the direction with the lower weight. For graphs
+    /// consisting of multiple disconnected components, a spanning forest with one tree per
+    /// component is returned.
+    pub fn minimum_spanning_tree(&self) -> Result<Self, Error> {
+        let mut edges = Vec::with_capacity(self.edges.len());
+        for (edge, weight) in self.edges.iter() {
+            edges.push((
+                *weight,
+                *edge,
+                edge.origin_cell()?,
+                edge.destination_cell()?,
+            ));
+        }
+        // the edge is included in the sort key to be independent of the iteration order
+        // of the edge map.
+        edges.sort_unstable_by_key(|(weight, edge, _, _)| (*weight, *edge));
+
+        // union-find over the nodes
+        let node_ids: H3CellMap<usize> = self
+            .nodes()?
+            .keys()
+            .enumerate()
+            .map(|(id, cell)| (*cell, id))
+            .collect();
+        let mut parents: Vec<usize> = (0..node_ids.len()).collect();
+
+        let mut tree = Self::new(self.h3_resolution);
+        for (weight, edge, origin_cell, destination_cell) in edges {
+            let origin_root = find_root(&mut parents, node_ids[&origin_cell]);
+            let destination_root = find_root(&mut parents, node_ids[&destination_cell]);
+            if origin_root != destination_root {
+                parents[origin_root] = destination_root;
+                tree.add_edge(edge, weight)?;
+            }
+        }
+        Ok(tree)
+    }
+}
+
+impl<W> H3EdgeGraph<W>
+where
+    W: Zero + Ord + Copy + Add + Send + Sync,
+{
+    /// The number of cells reachable from each of the `origins` without the aggregated
+    /// weights exceeding `max_weight`.
+    ///
+    /// The origin cells themselves are included in the counts. The origins are processed
+    /// in parallel.
+    pub fn accessibility_scores(
+        &self,
+        origins: &[H3Cell],
+        max_weight: W,
+    ) -> Result<H3CellMap<usize>, Error> {
+        origins
+            .par_iter()
+            .map(|origin| {
+                OutgoingEdges(self)
+                    .cells_within_weight_threshold(*origin, max_weight)
+                    .map(|reachable| (*origin, reachable.len()))
+            })
+            .collect()
+    }
+}
+
+impl<W> H3EdgeGraph<W>
+where
+    W: PartialOrd
+        + PartialEq
+        + Add
+        + Copy
+        + Div<Output = W>
+        + Mul<Output = W>
+        + Sub<Output = W>
+        + FromPrimitive,
+{
+    /// Add edges along the grid path connecting `cell_from` and `cell_to`.
+    ///
+    /// In contrast to [`H3EdgeGraph::add_edge_using_cells`] the cells do not need to be
+    /// neighbors. The `total_weight` is split evenly between all edges of the path. For
+    /// integer weights the remainder of the division is added to the last edge, so the
+    /// weights of the edges always sum up to `total_weight`. When both cells are the same,
+    /// no edges are added.
+    pub fn add_path_using_cells(
+        &mut self,
+        cell_from: H3Cell,
+        cell_to: H3Cell,
+        total_weight: W,
+    ) -> Result<(), Error> {
+        let cells = grid_path_cells(cell_from, cell_to)?;
+        let num_edges = cells.count().saturating_sub(1);
+        if num_edges == 0 {
+            return Ok(());
+        }
+        let to_weight = |n: usize| {
+            W::from_usize(n)
+                .ok_or_else(|| Error::Other(format!("number of edges {} not representable", n)))
+        };
+        let weight = total_weight / to_weight(num_edges)?;
+        let last_weight = total_weight - weight * to_weight(num_edges - 1)?;
+
+        let mut cells_iter = cells.iter().enumerate();
+        if let Some((_, mut previous_cell)) = cells_iter.next() {
+            for (cell_i, cell) in cells_iter {
+                let edge_weight = if cell_i == num_edges {
+                    last_weight
+                } else {
+                    weight
+                };
+                self.add_edge_using_cells(previous_cell, cell, edge_weight)?;
+                previous_cell = cell;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W> H3EdgeGraph<W>
+where
+    W: PartialOrd + PartialEq + Add + Copy + ToPrimitive,
+{
+    /// Sample weighted random walks through the graph, for example to be used for
+    /// learning graph embeddings.
+    ///
+    /// `num_walks` walks are started from each of the `start_cells`. Each walk contains at most
+    /// `walk_length` cells including its start cell. A walk ends early when it reaches a cell
+    /// without outgoing edges. The probability of following an edge is inversely proportional
+    /// to its weight; edges with weights which are not positive and finite are never followed.
+    ///
+    /// The walks are deterministic for a given `seed`.
+    pub fn random_walks(
+        &self,
+        start_cells: &[H3Cell],
+        walk_length: usize,
+        num_walks: usize,
+        seed: u64,
+    ) -> Vec<Vec<H3Cell>> {
+        // outgoing edges per cell as tuples of (destination cell, cumulative probability weight).
+        // The edges are sorted to be independent of the iteration order of the edge map.
+        let mut successors: H3CellMap<Vec<(H3Cell, f64)>> = H3CellMap::default();
+        let mut edges: Vec<_> = self
+            .edges
+            .iter()
+            .filter_map(|(edge, weight)| {
+                let weight = weight.to_f64()?;
+                if !(weight.is_finite() && weight > 0.0) {
+                    return None;
+                }
+                let origin_cell = edge.origin_cell().ok()?;
+                let destination_cell = edge.destination_cell().ok()?;
+                Some((origin_cell, destination_cell, weight.recip()))
+            })
+            .collect();
+        edges.sort_unstable_by_key(|(origin_cell, destination_cell, _)| {
+            (*origin_cell, *destination_cell)
+        });
+        for (origin_cell, destination_cell, probability_weight) in edges {
+            let cell_successors = successors.entry(origin_cell).or_default();
+            let cumulative = cell_successors.last().map_or(0.0, |(_, c)| *c) + probability_weight;
+            cell_successors.push((destination_cell, cumulative));
+        }
+
+        let mut rng = SplitMix64(seed);
+        let mut walks = Vec::with_capacity(start_cells.len() * num_walks);
+        for start_cell in start_cells {
+            for _ in 0..num_walks {
+                let mut walk = Vec::with_capacity(walk_length);
+                let mut current_cell = *start_cell;
+                if walk_length > 0 {
+                    walk.push(current_cell);
+                }
+                while walk.len() < walk_length {
+                    let cell_successors = match successors.get(&current_cell) {
+                        Some(cell_successors) => cell_successors,
+                        None => break,
+                    };
+                    let total = cell_successors.last().map_or(0.0, |(_, c)| *c);
+                    let threshold = rng.next_f64() * total;
+                    let position = cell_successors
+                        .partition_point(|(_, cumulative)| *cumulative <= threshold)
+                        .min(cell_successors.len() - 1);
+                    current_cell = cell_successors[position].0;
+                    walk.push(current_cell);
+                }
+                walks.push(walk);
+            }
+        }
+        walks
+    }
+}
+
+/// Minimal pseudo-random number generator to get reproducible random walks
+/// without depending on a specific `rand` version.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// uniformly distributed in `0.0..1.0`
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1_u64 << 53) as f64)
+    }
+}
+
+/// find the root of the set containing `id` in the union-find `parents`
+fn find_root(parents: &mut [usize], mut id: usize) -> usize {
+    while parents[id] != id {
+        // path halving
+        parents[id] = parents[parents[id]];
+        id = parents[id];
+    }
+    id
 }
 
 fn extract_nodes<W>(partition: &H3EdgeMap<W>) -> Result<H3CellMap<NodeType>, Error> {
@@ -189,6 +646,33 @@ where
     }
 }
 
+/// Adapter to use the algorithms based on [`GetCellEdges`] directly on a [`H3EdgeGraph`]
+/// without preparing it first.
+struct OutgoingEdges<'a, W>(&'a H3EdgeGraph<W>);
+
+impl<'a, W> GetCellEdges for OutgoingEdges<'a, W>
+where
+    W: Copy,
+{
+    type EdgeWeightType = W;
+
+    fn get_edges_originating_from(
+        &self,
+        cell: &H3Cell,
+    ) -> Result<Vec<(H3DirectedEdge, EdgeWeight<'_, Self::EdgeWeightType>)>, Error> {
+        Ok(cell
+            .directed_edges()?
+            .iter()
+            .filter_map(|edge| {
+                self.0
+                    .edges
+                    .get(&edge)
+                    .map(|weight| (edge, EdgeWeight::from(*weight)))
+            })
+            .collect())
+    }
+}
+
 impl<W> CoveredArea for H3EdgeGraph<W>
 where
     W: PartialOrd + PartialEq + Add + Copy,
@@ -256,9 +740,62 @@ where
     Ok(H3EdgeGraph {
         edges: downsampled_edges,
         h3_resolution: target_h3_resolution,
+        node_count: OnceLock::new(),
     })
 }
 
+/// change the resolution of a graph to a higher resolution
+///
+/// Each edge of `graph` gets replaced by a path of edges at `target_h3_resolution` connecting
+/// the center children of the origin- and destination cell of the edge. The weight of the path edges
+/// is determined by the `weight_split_fn` which gets passed the weight of the original edge and the number
+/// of edges in the path.
+///
+/// This is only an approximation as the path edges just follow the straight grid path between the center
+/// children, not the path the original edge was created from. The topology of the graph is preserved as
+/// the paths of edges sharing a cell also share that cells center child.
+pub fn refine_graph<W, F>(
+    graph: &H3EdgeGraph<W>,
+    target_h3_resolution: u8,
+    weight_split_fn: F,
+) -> Result<H3EdgeGraph<W>, Error>
+where
+    W: PartialOrd + PartialEq + Add + Copy,
+    F: Fn(W, usize) -> W,
+{
+    if target_h3_resolution <= graph.h3_resolution {
+        return Err(Error::TooLowH3Resolution(target_h3_resolution));
+    }
+    log::debug!(
+        "refining graph from r={} to r={}",
+        graph.h3_resolution,
+        target_h3_resolution
+    );
+
+    let mut refined_graph = H3EdgeGraph::new(target_h3_resolution);
+    for (edge, weight) in graph.edges.iter() {
+        let edge_cells = edge.cells()?;
+        let cells = grid_path_cells(
+            edge_cells.origin.center_child(target_h3_resolution)?,
+            edge_cells.destination.center_child(target_h3_resolution)?,
+        )?;
+        let num_edges = cells.count().saturating_sub(1);
+        if num_edges == 0 {
+            continue;
+        }
+        let path_weight = weight_split_fn(*weight, num_edges);
+
+        let mut cells_iter = cells.iter();
+        if let Some(mut previous_cell) = cells_iter.next() {
+            for cell in cells_iter {
+                refined_graph.add_edge_using_cells(previous_cell, cell, path_weight)?;
+                previous_cell = cell;
+            }
+        }
+    }
+    Ok(refined_graph)
+}
+
 pub trait H3EdgeGraphBuilder<W>
 where
     W: PartialOrd + PartialEq + Add + Copy,
@@ -272,9 +809,38 @@ mod tests {
 
     use geo_types::{Coord, LineString};
 
-    use h3ron::H3Cell;
+    use h3ron::{grid_path_cells, H3Cell};
 
-    use super::{downsample_graph, H3EdgeGraph, NodeType};
+    use super::{downsample_graph, refine_graph, H3EdgeGraph, NodeType};
+
+    #[test]
+    fn test_accessibility_scores() {
+        let res = 8;
+        let cells: Vec<_> = grid_path_cells(
+            H3Cell::from_coordinate(Coord::from((23.3, 12.3)), res).unwrap(),
+            H3Cell::from_coordinate(Coord::from((23.4, 12.3)), res).unwrap(),
+        )
+        .unwrap()
+        .iter()
+        .collect();
+        assert!(cells.len() > 10);
+
+        let mut graph = H3EdgeGraph::new(res);
+        for w in cells.windows(2) {
+            graph
+                .add_edge_using_cells_bidirectional(w[0], w[1], 1_u32)
+                .unwrap();
+        }
+
+        let central = cells[cells.len() / 2];
+        let peripheral = cells[0];
+        let scores = graph
+            .accessibility_scores(&[central, peripheral], 3)
+            .unwrap();
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores[&central], 7);
+        assert_eq!(scores[&peripheral], 4);
+    }
 
     #[test]
     fn test_downsample() {
@@ -298,6 +864,46 @@ mod tests {
         assert!(downsampled_graph.num_edges() < 20);
     }
 
+    #[test]
+    fn test_refine() {
+        let coarse_h3_res = 6;
+        let cells: Vec<_> = h3ron::line(
+            &LineString::from(vec![Coord::from((23.3, 12.3)), Coord::from((24.2, 12.2))]),
+            coarse_h3_res,
+        )
+        .unwrap()
+        .into();
+        assert!(cells.len() > 5);
+
+        let mut graph = H3EdgeGraph::new(coarse_h3_res);
+        for w in cells.windows(2) {
+            graph.add_edge_using_cells(w[0], w[1], 70).unwrap();
+        }
+
+        let target_h3_res = coarse_h3_res + 2;
+        let refined_graph = refine_graph(&graph, target_h3_res, |weight, num_edges| {
+            weight / num_edges as i32
+        })
+        .unwrap();
+        assert_eq!(refined_graph.h3_resolution, target_h3_res);
+        assert!(refined_graph.num_edges() > graph.num_edges());
+
+        // follow the edges from the first to the last cell of the line
+        let start = cells[0].center_child(target_h3_res).unwrap();
+        let end = cells[cells.len() - 1].center_child(target_h3_res).unwrap();
+        let mut cell = start;
+        let mut steps = 0;
+        while cell != end {
+            let edges = refined_graph.edges_from_cell(&cell).unwrap();
+            assert_eq!(edges.len(), 1);
+            cell = edges[0].0.destination_cell().unwrap();
+            steps += 1;
+        }
+        assert_eq!(steps, refined_graph.num_edges());
+
+        assert!(refine_graph(&graph, coarse_h3_res, |weight, _| weight).is_err());
+    }
+
     #[test]
     fn test_graph_nodes() {
         let res = 8;
@@ -332,4 +938,348 @@ mod tests {
         );
         assert_eq!(nodes.get(&edges2[0].1), Some(&NodeType::Destination));
     }
+
+    #[test]
+    fn test_add_path_using_cells() {
+        let res = 8;
+        let cell_from = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), res).unwrap();
+        let cell_to = H3Cell::from_coordinate(Coord::from((23.6, 12.1)), res).unwrap();
+        let grid_distance = cell_from.grid_distance_to(cell_to).unwrap();
+        assert!(grid_distance > 10);
+
+        let mut graph = H3EdgeGraph::new(res);
+        graph
+            .add_path_using_cells(cell_from, cell_to, 10.0 * grid_distance as f64)
+            .unwrap();
+        assert_eq!(graph.num_edges(), grid_distance);
+
+        // follow the chain of edges from the origin to the destination
+        let mut cell = cell_from;
+        let mut steps = 0;
+        while cell != cell_to {
+            let edges = graph.edges_from_cell(&cell).unwrap();
+            assert_eq!(edges.len(), 1);
+            let (edge, weight) = edges[0];
+            assert!((weight - 10.0).abs() < f64::EPSILON);
+            cell = edge.destination_cell().unwrap();
+            steps += 1;
+        }
+        assert_eq!(steps, grid_distance);
+        assert!(graph.edges_from_cell(&cell_to).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_add_path_using_cells_integer_remainder() {
+        let res = 8;
+        let cell_from = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), res).unwrap();
+        let cell_to = cell_from
+            .grid_ring_unsafe(3)
+            .unwrap()
+            .iter()
+            .next()
+            .unwrap();
+
+        let mut graph = H3EdgeGraph::new(res);
+        graph
+            .add_path_using_cells(cell_from, cell_to, 10_u32)
+            .unwrap();
+        assert_eq!(graph.num_edges(), 3);
+
+        // 10 / 3 is truncated to 3, the remainder goes to the last edge
+        let mut cell = cell_from;
+        let mut weights = vec![];
+        while cell != cell_to {
+            let (edge, weight) = graph.edges_from_cell(&cell).unwrap()[0];
+            weights.push(*weight);
+            cell = edge.destination_cell().unwrap();
+        }
+        assert_eq!(weights, vec![3, 3, 4]);
+    }
+
+    #[test]
+    fn test_to_travel_time() {
+        let res = 8;
+        let cell_from = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), res).unwrap();
+        let cell_to = H3Cell::from_coordinate(Coord::from((23.6, 12.1)), res).unwrap();
+
+        let mut graph = H3EdgeGraph::new(res);
+        graph
+            .add_path_using_cells(cell_from, cell_to, 100_u32)
+            .unwrap();
+
+        let speed_m_per_s = 10.0;
+        let travel_time_graph = graph.to_travel_time(speed_m_per_s).unwrap();
+        assert_eq!(travel_time_graph.num_edges(), graph.num_edges());
+        assert_eq!(travel_time_graph.h3_resolution, res);
+        for (edge, travel_time) in travel_time_graph.iter_edges() {
+            let expected = edge.cell_centroid_distance_m().unwrap() / speed_m_per_s;
+            assert!((travel_time - expected).abs() < 1e-9);
+        }
+
+        assert!(graph.to_travel_time(0.0).is_err());
+    }
+
+    fn weighted_test_graph(weights: &[u32]) -> H3EdgeGraph<u32> {
+        let res = 8;
+        let cell_from = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), res).unwrap();
+        let cell_to = H3Cell::from_coordinate(Coord::from((23.6, 12.1)), res).unwrap();
+
+        let mut graph = H3EdgeGraph::new(res);
+        for (edge, weight) in grid_path_cells(cell_from, cell_to)
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>()
+            .windows(2)
+            .map(|w| w[0].directed_edge_to(w[1]).unwrap())
+            .zip(weights.iter())
+        {
+            graph.add_edge(edge, *weight).unwrap();
+        }
+        assert_eq!(graph.num_edges(), weights.len());
+        graph
+    }
+
+    #[test]
+    fn test_to_multilinestring_filtered() {
+        let graph = weighted_test_graph(&[1, 5, 10, 50, 5000]);
+        let mls = graph.to_multilinestring_filtered(|w| *w >= 10).unwrap();
+        assert_eq!(mls.0.len(), 3);
+        assert!(mls.0.iter().all(|ls| ls.0.len() == 2));
+
+        assert!(graph
+            .to_multilinestring_filtered(|w| *w > 10000)
+            .unwrap()
+            .0
+            .is_empty());
+    }
+
+    #[test]
+    fn test_diff() {
+        let graph = weighted_test_graph(&[1, 2, 3, 4]);
+        let mut edges: Vec<_> = graph.edges.keys().copied().collect();
+        edges.sort_unstable();
+
+        let mut other = graph.clone();
+        other.edges.remove(&edges[0]);
+        *other.edges.get_mut(&edges[1]).unwrap() += 10;
+        let added_edge = edges[3].reversed().unwrap();
+        other.add_edge(added_edge, 7).unwrap();
+
+        let graph_diff = graph.diff(&other);
+        assert_eq!(graph_diff.removed.len(), 1);
+        assert_eq!(
+            graph_diff.removed.get(&edges[0]),
+            graph.edges.get(&edges[0])
+        );
+        assert_eq!(graph_diff.changed.len(), 1);
+        let old_weight = graph.edges[&edges[1]];
+        assert_eq!(
+            graph_diff.changed.get(&edges[1]),
+            Some(&(old_weight, old_weight + 10))
+        );
+        assert_eq!(graph_diff.added.len(), 1);
+        assert_eq!(graph_diff.added.get(&added_edge), Some(&7));
+
+        assert!(graph.diff(&graph).is_empty());
+    }
+
+    #[test]
+    fn test_clamp_weights() {
+        let mut graph = weighted_test_graph(&[1, 5, 10, 50, 5000]);
+        graph.clamp_weights(5, 100);
+
+        let mut weights: Vec<_> = graph.edges.values().copied().collect();
+        weights.sort_unstable();
+        assert_eq!(weights, vec![5, 5, 10, 50, 100]);
+    }
+
+    #[test]
+    fn test_remove_weight_outliers() {
+        let mut graph = weighted_test_graph(&[1, 10, 11, 12, 13, 14, 15, 16, 17, 50000]);
+        graph.remove_weight_outliers(0.1, 0.9).unwrap();
+
+        let mut weights: Vec<_> = graph.edges.values().copied().collect();
+        weights.sort_unstable();
+        assert_eq!(weights, vec![10, 11, 12, 13, 14, 15, 16, 17]);
+
+        assert!(graph.remove_weight_outliers(0.9, 0.1).is_err());
+        assert!(graph.remove_weight_outliers(-0.1, 0.5).is_err());
+    }
+
+    #[cfg(feature = "petgraph")]
+    #[test]
+    fn test_to_petgraph() {
+        let res = 8;
+        let cell_from = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), res).unwrap();
+        let cell_to = H3Cell::from_coordinate(Coord::from((23.6, 12.1)), res).unwrap();
+
+        let mut graph = H3EdgeGraph::new(res);
+        graph
+            .add_path_using_cells(cell_from, cell_to, 100_u32)
+            .unwrap();
+        let known_edge = cell_to.directed_edges().unwrap().first().unwrap();
+        graph.add_edge(known_edge, 7).unwrap();
+
+        let (pgraph, node_indexes) = graph.to_petgraph().unwrap();
+        assert_eq!(pgraph.node_count(), graph.num_nodes().unwrap());
+        assert_eq!(pgraph.edge_count(), graph.num_edges());
+        assert_eq!(node_indexes.len(), pgraph.node_count());
+
+        let edge_cells = known_edge.cells().unwrap();
+        let origin_index = node_indexes[&edge_cells.origin];
+        let destination_index = node_indexes[&edge_cells.destination];
+        assert_eq!(pgraph[origin_index], edge_cells.origin);
+        assert_eq!(pgraph[destination_index], edge_cells.destination);
+        let pgraph_edge = pgraph.find_edge(origin_index, destination_index).unwrap();
+        assert_eq!(pgraph[pgraph_edge], 7);
+    }
+
+    #[test]
+    fn test_random_walks() {
+        let res = 8;
+        let center = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), res).unwrap();
+        let mut graph = H3EdgeGraph::new(res);
+        for cell in center.grid_disk(3).unwrap().iter() {
+            for edge in cell.directed_edges().unwrap().iter() {
+                let weight = if edge.destination_cell().unwrap() == center {
+                    1
+                } else {
+                    10
+                };
+                graph.add_edge(edge, weight).unwrap();
+            }
+        }
+
+        let start_cells = [center, center.grid_ring_unsafe(2).unwrap().first().unwrap()];
+        let walks = graph.random_walks(&start_cells, 20, 5, 42);
+        assert_eq!(walks.len(), 10);
+        for (i, walk) in walks.iter().enumerate() {
+            assert!(!walk.is_empty());
+            assert!(walk.len() <= 20);
+            assert_eq!(walk[0], start_cells[i / 5]);
+            for w in walk.windows(2) {
+                assert!(graph
+                    .edge_weight(&w[0].directed_edge_to(w[1]).unwrap())
+                    .is_some());
+            }
+        }
+
+        // reproducible
+        assert_eq!(walks, graph.random_walks(&start_cells, 20, 5, 42));
+        assert_ne!(walks, graph.random_walks(&start_cells, 20, 5, 43));
+
+        // cells without outgoing edges end the walk
+        let isolated_cell = H3Cell::from_coordinate(Coord::from((40.0, 10.0)), res).unwrap();
+        assert_eq!(
+            graph.random_walks(&[isolated_cell], 20, 2, 42),
+            vec![vec![isolated_cell], vec![isolated_cell]]
+        );
+    }
+
+    #[test]
+    fn test_node_count_cached() {
+        let mut graph = weighted_test_graph(&[1, 2, 3]);
+        assert_eq!(
+            graph.node_count_cached().unwrap(),
+            graph.num_nodes().unwrap()
+        );
+        assert_eq!(graph.node_count_cached().unwrap(), 4);
+
+        let last_cell = graph
+            .nodes()
+            .unwrap()
+            .into_iter()
+            .find(|(_, node_type)| *node_type == NodeType::Destination)
+            .unwrap()
+            .0;
+        let next_cell = last_cell
+            .grid_ring_unsafe(1)
+            .unwrap()
+            .iter()
+            .find(|cell| graph.nodes().unwrap().get(cell).is_none())
+            .unwrap();
+        graph.add_edge_using_cells(last_cell, next_cell, 4).unwrap();
+        assert_eq!(
+            graph.node_count_cached().unwrap(),
+            graph.num_nodes().unwrap()
+        );
+        assert_eq!(graph.node_count_cached().unwrap(), 5);
+
+        graph.remove_weight_outliers(0.0, 0.5).unwrap();
+        assert_eq!(
+            graph.node_count_cached().unwrap(),
+            graph.num_nodes().unwrap()
+        );
+
+        // direct modifications changing the number of edges are detected
+        graph.edges.clear();
+        assert_eq!(graph.node_count_cached().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_minimum_spanning_tree() {
+        let res = 8;
+        let center = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), res).unwrap();
+        let ring = center
+            .grid_ring_unsafe(1)
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+        assert_eq!(ring.len(), 6);
+
+        // a cycle of bidirectional edges around the center plus a spoke to the center
+        let mut graph = H3EdgeGraph::new(res);
+        for (i, cell) in ring.iter().enumerate() {
+            let next_cell = ring[(i + 1) % ring.len()];
+            graph
+                .add_edge_using_cells_bidirectional(*cell, next_cell, (i as u32 + 1) * 10)
+                .unwrap();
+        }
+        graph.add_edge_using_cells(ring[0], center, 5).unwrap();
+        graph.add_edge_using_cells(center, ring[0], 3).unwrap();
+
+        let tree = graph.minimum_spanning_tree().unwrap();
+        let num_nodes = graph.num_nodes().unwrap();
+        assert_eq!(num_nodes, 7);
+        assert_eq!(tree.num_nodes().unwrap(), num_nodes);
+        assert_eq!(tree.num_edges(), num_nodes - 1);
+
+        // the most expensive connection of the cycle is dropped
+        let total_weight = |g: &H3EdgeGraph<u32>| g.edges.values().sum::<u32>();
+        assert_eq!(total_weight(&tree), 3 + 10 + 20 + 30 + 40 + 50);
+        assert!(total_weight(&tree) < total_weight(&graph));
+
+        // disconnected components yield a spanning forest
+        let other_cell = H3Cell::from_coordinate(Coord::from((40.0, 10.0)), res).unwrap();
+        let other_neighbor = other_cell.grid_ring_unsafe(1).unwrap().first().unwrap();
+        graph
+            .add_edge_using_cells_bidirectional(other_cell, other_neighbor, 1)
+            .unwrap();
+        let forest = graph.minimum_spanning_tree().unwrap();
+        assert_eq!(forest.num_edges(), num_nodes - 1 + 1);
+    }
+
+    #[test]
+    fn test_subgraph() {
+        let graph = weighted_test_graph(&[1, 2, 3, 4, 5]);
+        let mut edges = graph.iter_edges().collect::<Vec<_>>();
+        edges.sort_unstable_by_key(|(_, weight)| **weight);
+        let line_cells = std::iter::once(edges[0].0.origin_cell().unwrap())
+            .chain(
+                edges
+                    .iter()
+                    .map(|(edge, _)| edge.destination_cell().unwrap()),
+            )
+            .collect::<Vec<_>>();
+        assert_eq!(line_cells.len(), 6);
+
+        // the inner cells of the line
+        let cells = line_cells[1..5].iter().collect();
+        let subgraph = graph.subgraph(&cells).unwrap();
+        assert_eq!(subgraph.h3_resolution, graph.h3_resolution);
+        assert_eq!(subgraph.num_edges(), 3);
+        let mut weights = subgraph.edges.values().copied().collect::<Vec<_>>();
+        weights.sort_unstable();
+        assert_eq!(weights, vec![2, 3, 4]);
+    }
 }
@@ -1,18 +1,25 @@
 use serde::Serialize;
 
 use crate::error::Error;
-pub use h3edge::{H3EdgeGraph, H3EdgeGraphBuilder};
+pub use contracted::ContractedGraph;
+pub use h3edge::{GraphDiff, H3EdgeGraph, H3EdgeGraphBuilder};
 use h3ron::{H3Cell, H3DirectedEdge};
+pub use multimodal::{ModeView, MultiModalGraph};
 use node::NodeType;
 pub use prepared::PreparedH3EdgeGraph;
+pub use temporal::{TemporalH3EdgeGraph, TimeBucketView};
 
 use crate::graph::longedge::LongEdge;
 
+pub mod builder;
+pub mod contracted;
 pub mod h3edge;
 pub mod longedge;
 pub mod modifiers;
+pub mod multimodal;
 pub mod node;
 pub mod prepared;
+pub mod temporal;
 
 #[derive(Serialize)]
 pub struct GraphStats {
@@ -0,0 +1,255 @@
+//! Graphs with multiple modes of transport sharing the same edges.
+//!
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::ops::Add;
+
+use num_traits::Zero;
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+
+use h3ron::collections::{H3CellMap, H3EdgeMap, HashMap};
+use h3ron::{H3Cell, H3DirectedEdge, HasH3Resolution, Index};
+
+use crate::algorithm::path::Path;
+use crate::algorithm::shortest_path::{ShortestPath, ShortestPathOptions};
+use crate::error::Error;
+use crate::graph::node::NodeType;
+use crate::graph::{EdgeWeight, GetCellEdges, GetCellNode};
+
+/// The weights of an edge for the modes it can be traversed with. Most edges
+/// are expected to be used by only a few modes.
+type ModeWeights<M, W> = SmallVec<[(M, W); 2]>;
+
+/// A graph where each edge can be traversed using multiple modes - like walking or
+/// cycling - each with its own weight.
+///
+/// Routing happens using a single mode at a time using the [`ModeView`] returned
+/// by [`MultiModalGraph::mode_view`].
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(bound(
+    serialize = "W: Serialize, M: Serialize + Eq + Hash",
+    deserialize = "W: Deserialize<'de>, M: Deserialize<'de> + Eq + Hash"
+))]
+pub struct MultiModalGraph<W, M> {
+    edges: H3EdgeMap<ModeWeights<M, W>>,
+    nodes: HashMap<M, H3CellMap<NodeType>>,
+    h3_resolution: u8,
+}
+
+impl<W, M> MultiModalGraph<W, M>
+where
+    W: PartialOrd + Copy,
+    M: Eq + Hash + Clone,
+{
+    pub fn new(h3_resolution: u8) -> Self {
+        Self {
+            edges: Default::default(),
+            nodes: Default::default(),
+            h3_resolution,
+        }
+    }
+
+    /// The number of edges, regardless of the number of modes they can be traversed with.
+    pub fn num_edges(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// The modes contained in the graph.
+    pub fn modes(&self) -> impl Iterator<Item = &M> {
+        self.nodes.keys()
+    }
+
+    /// Add `edge` to the graph to be traversable using `mode`.
+    ///
+    /// When the edge already exists for `mode`, the lower weight takes precedence.
+    pub fn add_edge(&mut self, edge: H3DirectedEdge, mode: M, weight: W) -> Result<(), Error> {
+        if edge.resolution() != self.h3_resolution {
+            return Err(Error::MixedH3Resolutions(
+                self.h3_resolution,
+                edge.resolution(),
+            ));
+        }
+        let cell_from = edge.origin_cell()?;
+        let cell_to = edge.destination_cell()?;
+
+        let mode_weights = self.edges.entry(edge).or_default();
+        match mode_weights
+            .iter_mut()
+            .find(|(existing_mode, _)| existing_mode == &mode)
+        {
+            Some((_, existing_weight)) => {
+                if weight < *existing_weight {
+                    *existing_weight = weight;
+                }
+            }
+            None => mode_weights.push((mode.clone(), weight)),
+        }
+
+        let mode_nodes = self.nodes.entry(mode).or_default();
+        mode_nodes
+            .entry(cell_from)
+            .and_modify(|node_type| *node_type += NodeType::Origin)
+            .or_insert(NodeType::Origin);
+        mode_nodes
+            .entry(cell_to)
+            .and_modify(|node_type| *node_type += NodeType::Destination)
+            .or_insert(NodeType::Destination);
+        Ok(())
+    }
+
+    /// The weight of `edge` when traversed using `mode`.
+    pub fn edge_weight(&self, edge: &H3DirectedEdge, mode: &M) -> Option<W> {
+        self.edges.get(edge).and_then(|mode_weights| {
+            mode_weights
+                .iter()
+                .find(|(edge_mode, _)| edge_mode == mode)
+                .map(|(_, weight)| *weight)
+        })
+    }
+
+    /// A view on the graph only containing the edges traversable using `mode`.
+    ///
+    /// The view can be used with the algorithms of this crate, for example
+    /// with [`ShortestPath`]. For modes not contained in the graph, the view is empty.
+    pub fn mode_view<'a>(&'a self, mode: &'a M) -> ModeView<'a, W, M> {
+        ModeView { graph: self, mode }
+    }
+
+    /// Shortest paths using only the edges traversable using `mode`.
+    ///
+    /// See [`ShortestPath::shortest_path`].
+    pub fn shortest_path_by_mode<I, OPT>(
+        &self,
+        mode: &M,
+        origin_cell: H3Cell,
+        destination_cells: I,
+        options: &OPT,
+    ) -> Result<Vec<Path<W>>, Error>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<H3Cell>,
+        OPT: ShortestPathOptions,
+        W: Add + Ord + Zero,
+    {
+        self.mode_view(mode)
+            .shortest_path(origin_cell, destination_cells, options)
+    }
+}
+
+impl<W, M> HasH3Resolution for MultiModalGraph<W, M> {
+    fn h3_resolution(&self) -> u8 {
+        self.h3_resolution
+    }
+}
+
+/// View on a [`MultiModalGraph`] presenting only the edges of a single mode.
+pub struct ModeView<'a, W, M> {
+    graph: &'a MultiModalGraph<W, M>,
+    mode: &'a M,
+}
+
+impl<'a, W, M> ModeView<'a, W, M> {
+    pub const fn mode(&self) -> &M {
+        self.mode
+    }
+}
+
+impl<'a, W, M> HasH3Resolution for ModeView<'a, W, M> {
+    fn h3_resolution(&self) -> u8 {
+        self.graph.h3_resolution
+    }
+}
+
+impl<'a, W, M> GetCellNode for ModeView<'a, W, M>
+where
+    M: Eq + Hash,
+{
+    fn get_cell_node(&self, cell: &H3Cell) -> Option<NodeType> {
+        self.graph
+            .nodes
+            .get(self.mode)
+            .and_then(|mode_nodes| mode_nodes.get(cell).copied())
+    }
+}
+
+impl<'a, W, M> GetCellEdges for ModeView<'a, W, M>
+where
+    W: Copy,
+    M: Eq,
+{
+    type EdgeWeightType = W;
+
+    fn get_edges_originating_from(
+        &self,
+        cell: &H3Cell,
+    ) -> Result<Vec<(H3DirectedEdge, EdgeWeight<'_, Self::EdgeWeightType>)>, Error> {
+        Ok(cell
+            .directed_edges()?
+            .iter()
+            .filter_map(|edge| {
+                self.graph.edges.get(&edge).and_then(|mode_weights| {
+                    mode_weights
+                        .iter()
+                        .find(|(edge_mode, _)| edge_mode == self.mode)
+                        .map(|(_, weight)| (edge, (*weight).into()))
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::Coord;
+
+    use h3ron::{grid_path_cells, H3Cell};
+
+    use crate::algorithm::shortest_path::DefaultShortestPathOptions;
+
+    use super::MultiModalGraph;
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    enum Mode {
+        Walk,
+        Bike,
+        Car,
+    }
+
+    #[test]
+    fn test_route_by_mode() {
+        let res = 8;
+        let origin = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), res).unwrap();
+        let destination = H3Cell::from_coordinate(Coord::from((23.4, 12.3)), res).unwrap();
+        let cells = grid_path_cells(origin, destination)
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+
+        let mut graph = MultiModalGraph::new(res);
+        for w in cells.windows(2) {
+            let edge = w[0].directed_edge_to(w[1]).unwrap();
+            graph.add_edge(edge, Mode::Walk, 10_u32).unwrap();
+            graph.add_edge(edge, Mode::Bike, 3_u32).unwrap();
+        }
+        let num_edges = cells.len() - 1;
+        assert_eq!(graph.num_edges(), num_edges);
+        assert_eq!(graph.modes().count(), 2);
+
+        let options = DefaultShortestPathOptions::default();
+        for (mode, edge_weight) in [(Mode::Walk, 10), (Mode::Bike, 3)] {
+            let paths = graph
+                .shortest_path_by_mode(&mode, origin, [destination], &options)
+                .unwrap();
+            assert_eq!(paths.len(), 1);
+            assert_eq!(paths[0].cost, edge_weight * num_edges as u32);
+            assert_eq!(paths[0].directed_edge_path.cells().unwrap(), cells);
+        }
+
+        // the mode is not part of the graph
+        assert!(graph
+            .shortest_path_by_mode(&Mode::Car, origin, [destination], &options)
+            .unwrap()
+            .is_empty());
+    }
+}
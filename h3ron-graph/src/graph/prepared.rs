@@ -436,13 +436,12 @@ where
     W: PartialOrd + PartialEq + Add + Copy + Ord + Zero,
 {
     fn from(prepared_graph: PreparedH3EdgeGraph<W>) -> Self {
-        Self {
-            edges: prepared_graph
-                .iter_edges()
-                .map(|(edge, edge_value)| (edge, edge_value.weight))
-                .collect(),
-            h3_resolution: prepared_graph.h3_resolution,
-        }
+        let mut graph = Self::new(prepared_graph.h3_resolution);
+        graph.edges = prepared_graph
+            .iter_edges()
+            .map(|(edge, edge_value)| (edge, edge_value.weight))
+            .collect();
+        graph
     }
 }
 
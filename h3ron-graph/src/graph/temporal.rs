@@ -0,0 +1,260 @@
+//! Graphs with time-dependent edge weights.
+//!
+use std::borrow::Borrow;
+use std::ops::Add;
+
+use num_traits::Zero;
+use serde::{Deserialize, Serialize};
+
+use h3ron::collections::hashbrown::hash_map::Entry;
+use h3ron::collections::{H3CellMap, H3EdgeMap};
+use h3ron::{H3Cell, H3DirectedEdge, HasH3Resolution, Index};
+
+use crate::algorithm::path::Path;
+use crate::algorithm::shortest_path::{ShortestPath, ShortestPathOptions};
+use crate::error::Error;
+use crate::graph::node::NodeType;
+use crate::graph::{EdgeWeight, GetCellEdges, GetCellNode};
+
+/// A graph storing a separate weight for each time bucket per edge.
+///
+/// This allows modelling conditions changing over time, like traffic depending on the time
+/// of the day. Routing happens on a single time bucket at a time using the
+/// [`TimeBucketView`] returned by [`TemporalH3EdgeGraph::at_time_bucket`].
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TemporalH3EdgeGraph<W> {
+    edges: H3EdgeMap<Vec<W>>,
+    nodes: H3CellMap<NodeType>,
+    h3_resolution: u8,
+    num_time_buckets: usize,
+}
+
+impl<W> TemporalH3EdgeGraph<W>
+where
+    W: PartialOrd + Copy,
+{
+    pub fn new(h3_resolution: u8, num_time_buckets: usize) -> Self {
+        Self {
+            edges: Default::default(),
+            nodes: Default::default(),
+            h3_resolution,
+            num_time_buckets,
+        }
+    }
+
+    pub const fn num_time_buckets(&self) -> usize {
+        self.num_time_buckets
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Add an edge with one weight per time bucket.
+    ///
+    /// When the edge already exists, the lower weight takes precedence for each
+    /// time bucket.
+    pub fn add_edge(&mut self, edge: H3DirectedEdge, weights: Vec<W>) -> Result<(), Error> {
+        if weights.len() != self.num_time_buckets {
+            return Err(Error::Other(format!(
+                "expected {} weights, found {}",
+                self.num_time_buckets,
+                weights.len()
+            )));
+        }
+        if edge.resolution() != self.h3_resolution {
+            return Err(Error::MixedH3Resolutions(
+                self.h3_resolution,
+                edge.resolution(),
+            ));
+        }
+        let cell_from = edge.origin_cell()?;
+        let cell_to = edge.destination_cell()?;
+
+        match self.edges.entry(edge) {
+            Entry::Occupied(mut occ) => {
+                for (existing_weight, weight) in occ.get_mut().iter_mut().zip(weights) {
+                    if weight < *existing_weight {
+                        *existing_weight = weight;
+                    }
+                }
+            }
+            Entry::Vacant(vac) => {
+                vac.insert(weights);
+            }
+        }
+        self.nodes
+            .entry(cell_from)
+            .and_modify(|node_type| *node_type += NodeType::Origin)
+            .or_insert(NodeType::Origin);
+        self.nodes
+            .entry(cell_to)
+            .and_modify(|node_type| *node_type += NodeType::Destination)
+            .or_insert(NodeType::Destination);
+        Ok(())
+    }
+
+    /// The weights of `edge` for all time buckets.
+    pub fn edge_weights(&self, edge: &H3DirectedEdge) -> Option<&[W]> {
+        self.edges.get(edge).map(Vec::as_slice)
+    }
+
+    /// A view on the graph using only the weights of `time_bucket`.
+    ///
+    /// The view can be used with the algorithms of this crate, for example
+    /// with [`ShortestPath`].
+    pub fn at_time_bucket(&self, time_bucket: usize) -> Result<TimeBucketView<'_, W>, Error> {
+        if time_bucket >= self.num_time_buckets {
+            return Err(Error::Other(format!(
+                "time bucket {} out of range, the graph has {} time buckets",
+                time_bucket, self.num_time_buckets
+            )));
+        }
+        Ok(TimeBucketView {
+            graph: self,
+            time_bucket,
+        })
+    }
+
+    /// Shortest paths using the weights of `time_bucket`.
+    ///
+    /// See [`ShortestPath::shortest_path`].
+    pub fn shortest_path_at<I, OPT>(
+        &self,
+        time_bucket: usize,
+        origin_cell: H3Cell,
+        destination_cells: I,
+        options: &OPT,
+    ) -> Result<Vec<Path<W>>, Error>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<H3Cell>,
+        OPT: ShortestPathOptions,
+        W: Add + Ord + Zero,
+    {
+        self.at_time_bucket(time_bucket)?
+            .shortest_path(origin_cell, destination_cells, options)
+    }
+}
+
+impl<W> HasH3Resolution for TemporalH3EdgeGraph<W> {
+    fn h3_resolution(&self) -> u8 {
+        self.h3_resolution
+    }
+}
+
+/// View on a [`TemporalH3EdgeGraph`] presenting the weights of a single time bucket.
+pub struct TimeBucketView<'a, W> {
+    graph: &'a TemporalH3EdgeGraph<W>,
+    time_bucket: usize,
+}
+
+impl<'a, W> TimeBucketView<'a, W> {
+    pub const fn time_bucket(&self) -> usize {
+        self.time_bucket
+    }
+}
+
+impl<'a, W> HasH3Resolution for TimeBucketView<'a, W> {
+    fn h3_resolution(&self) -> u8 {
+        self.graph.h3_resolution
+    }
+}
+
+impl<'a, W> GetCellNode for TimeBucketView<'a, W> {
+    fn get_cell_node(&self, cell: &H3Cell) -> Option<NodeType> {
+        self.graph.nodes.get(cell).copied()
+    }
+}
+
+impl<'a, W: Copy> GetCellEdges for TimeBucketView<'a, W> {
+    type EdgeWeightType = W;
+
+    fn get_edges_originating_from(
+        &self,
+        cell: &H3Cell,
+    ) -> Result<Vec<(H3DirectedEdge, EdgeWeight<'_, Self::EdgeWeightType>)>, Error> {
+        Ok(cell
+            .directed_edges()?
+            .iter()
+            .filter_map(|edge| {
+                self.graph
+                    .edges
+                    .get(&edge)
+                    .map(|weights| (edge, weights[self.time_bucket].into()))
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::Coord;
+
+    use h3ron::H3Cell;
+
+    use crate::algorithm::shortest_path::DefaultShortestPathOptions;
+
+    use super::TemporalH3EdgeGraph;
+
+    #[test]
+    fn test_route_depends_on_time_bucket() {
+        let res = 8;
+        let origin = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), res).unwrap();
+        let ring1 = origin
+            .grid_ring_unsafe(1)
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+
+        // a destination which can be reached via two different cells of the first ring
+        let (destination, via) = origin
+            .grid_ring_unsafe(2)
+            .unwrap()
+            .iter()
+            .find_map(|cell| {
+                let via = ring1
+                    .iter()
+                    .filter(|r| r.are_neighbor_cells(cell).unwrap())
+                    .copied()
+                    .collect::<Vec<_>>();
+                (via.len() == 2).then_some((cell, via))
+            })
+            .unwrap();
+
+        let mut graph = TemporalH3EdgeGraph::new(res, 2);
+        let weights = [vec![1_u32, 10], vec![10, 1]];
+        for (via_cell, weights) in via.iter().zip(weights) {
+            graph
+                .add_edge(origin.directed_edge_to(*via_cell).unwrap(), weights.clone())
+                .unwrap();
+            graph
+                .add_edge(via_cell.directed_edge_to(destination).unwrap(), weights)
+                .unwrap();
+        }
+        assert_eq!(graph.num_edges(), 4);
+        assert_eq!(graph.num_nodes(), 4);
+
+        let options = DefaultShortestPathOptions::default();
+        for (time_bucket, via_cell) in via.iter().enumerate() {
+            let paths = graph
+                .shortest_path_at(time_bucket, origin, [destination], &options)
+                .unwrap();
+            assert_eq!(paths.len(), 1);
+            assert_eq!(paths[0].cost, 2);
+            assert_eq!(
+                paths[0].directed_edge_path.cells().unwrap(),
+                vec![origin, *via_cell, destination]
+            );
+        }
+
+        assert!(graph.at_time_bucket(2).is_err());
+        assert!(graph
+            .add_edge(origin.directed_edge_to(ring1[0]).unwrap(), vec![1])
+            .is_err());
+    }
+}
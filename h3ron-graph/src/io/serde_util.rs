@@ -76,11 +76,71 @@ where
     Ok(deserialized)
 }
 
+/// magic bytes identifying serialized graphs
+const GRAPH_MAGIC: [u8; 6] = *b"H3RONG";
+
+/// current version of the serialization format of graphs
+pub const GRAPH_FORMAT_VERSION: u16 = 1;
+
+/// Serialize a graph prefixed by a header containing a magic byte sequence and
+/// the [`GRAPH_FORMAT_VERSION`].
+///
+/// The header allows [`deserialize_graph_from`] to reject files written using an
+/// incompatible layout with [`Error::UnsupportedGraphVersion`] instead of failing
+/// somewhere during deserialization.
+///
+/// When `compress` is set to `true` LZ4 compression is applied to the graph, the header
+/// is always written uncompressed.
+pub fn serialize_graph_into<W, T>(mut writer: W, graph: &T, compress: bool) -> Result<(), Error>
+where
+    W: io::Write,
+    T: Serialize + ?Sized,
+{
+    writer.write_all(&GRAPH_MAGIC)?;
+    writer.write_all(&GRAPH_FORMAT_VERSION.to_le_bytes())?;
+    serialize_into(writer, graph, compress)
+}
+
+/// deserialize a graph written by [`serialize_graph_into`].
+pub fn deserialize_graph_from<R, T>(mut reader: R) -> Result<T, Error>
+where
+    R: io::Read,
+    T: serde::de::DeserializeOwned,
+{
+    let mut magic = [0u8; GRAPH_MAGIC.len()];
+    reader.read_exact(&mut magic)?;
+    if magic != GRAPH_MAGIC {
+        return Err(Error::IOError(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "not a serialized graph - magic bytes do not match",
+        )));
+    }
+
+    let mut version = [0u8; 2];
+    reader.read_exact(&mut version)?;
+    let version = u16::from_le_bytes(version);
+    if version != GRAPH_FORMAT_VERSION {
+        return Err(Error::UnsupportedGraphVersion(version));
+    }
+
+    let mut payload = vec![];
+    reader.read_to_end(&mut payload)?;
+    deserialize_from_byte_slice(&payload)
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::Cursor;
 
-    use crate::io::serde_util::{deserialize_from, serialize_into};
+    use geo_types::Coord;
+
+    use h3ron::H3Cell;
+
+    use crate::error::Error;
+    use crate::graph::H3EdgeGraph;
+    use crate::io::serde_util::{
+        deserialize_from, deserialize_graph_from, serialize_graph_into, serialize_into,
+    };
 
     fn roundtrip(compress: bool) {
         let data = vec![1_i32, 2, 3, 4];
@@ -100,4 +160,45 @@ mod tests {
     fn test_roundtrip_compression() {
         roundtrip(true);
     }
+
+    fn small_graph() -> H3EdgeGraph<u32> {
+        let cell = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), 8).unwrap();
+        let mut graph = H3EdgeGraph::new(8);
+        for edge in cell.directed_edges().unwrap().iter() {
+            graph.add_edge(edge, 3).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_graph_roundtrip() {
+        for compress in [false, true] {
+            let graph = small_graph();
+            let mut graph_bytes: Vec<u8> = vec![];
+            serialize_graph_into(Cursor::new(&mut graph_bytes), &graph, compress).unwrap();
+            assert_eq!(&graph_bytes[..6], b"H3RONG");
+            assert_eq!(&graph_bytes[6..8], &1_u16.to_le_bytes());
+
+            let graph2: H3EdgeGraph<u32> = deserialize_graph_from(graph_bytes.as_slice()).unwrap();
+            assert_eq!(graph2.h3_resolution, graph.h3_resolution);
+            assert_eq!(graph2.edges, graph.edges);
+        }
+    }
+
+    #[test]
+    fn test_graph_bad_magic() {
+        let mut graph_bytes: Vec<u8> = vec![];
+        serialize_into(Cursor::new(&mut graph_bytes), &small_graph(), false).unwrap();
+        let result: Result<H3EdgeGraph<u32>, _> = deserialize_graph_from(graph_bytes.as_slice());
+        assert!(matches!(result, Err(Error::IOError(_))));
+    }
+
+    #[test]
+    fn test_graph_unsupported_version() {
+        let mut graph_bytes: Vec<u8> = vec![];
+        serialize_graph_into(Cursor::new(&mut graph_bytes), &small_graph(), false).unwrap();
+        graph_bytes[6..8].copy_from_slice(&2_u16.to_le_bytes());
+        let result: Result<H3EdgeGraph<u32>, _> = deserialize_graph_from(graph_bytes.as_slice());
+        assert!(matches!(result, Err(Error::UnsupportedGraphVersion(2))));
+    }
 }
@@ -2,6 +2,7 @@
 //!
 //! * **io_osm**: Enables parsing of OpenStreetMap files.
 //! * **io_serde_util**: Convenience serialization helpers.
+//! * **petgraph**: Export graphs to [petgraph](https://github.com/petgraph/petgraph).
 
 #![warn(
     clippy::all,
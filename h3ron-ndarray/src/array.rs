@@ -1,13 +1,16 @@
 use std::cmp::min;
 use std::hash::Hash;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::Sender;
 
 use geo_types::{Coord, Rect};
 use log::debug;
 use ndarray::{ArrayView2, Axis};
 use rayon::prelude::*;
+use smallvec::SmallVec;
 
 use h3ron::collections::HashMap;
-use h3ron::{collections::CompactedCellVec, ToCoordinate, ToH3Cells};
+use h3ron::{collections::CompactedCellVec, H3Cell, ToCoordinate, ToH3Cells};
 
 use crate::resolution::{nearest_h3_resolution, ResolutionSearchMode};
 use crate::{error::Error, transform::Transform};
@@ -124,6 +127,57 @@ where
         .collect::<Vec<_>>()
 }
 
+/// create tiles of `rect_size` covering an array of the given `shape` completely
+fn rects_covering_array(
+    shape: &[usize],
+    axis_order: AxisOrder,
+    rect_size: usize,
+) -> Vec<Rect<f64>> {
+    let x_size = shape[axis_order.x_axis()];
+    let y_size = shape[axis_order.y_axis()];
+    (0..((x_size as f64 / rect_size as f64).ceil() as usize))
+        .flat_map(move |r_x| {
+            (0..((y_size as f64 / rect_size as f64).ceil() as usize)).map(move |r_y| {
+                Rect::new(
+                    Coord {
+                        x: (r_x * rect_size) as f64,
+                        y: (r_y * rect_size) as f64,
+                    },
+                    Coord {
+                        x: (min(x_size, (r_x + 1) * rect_size)) as f64,
+                        y: (min(y_size, (r_y + 1) * rect_size)) as f64,
+                    },
+                )
+            })
+        })
+        .collect()
+}
+
+/// find the array element for the coordinate of the `cell`
+///
+/// Returns `None` when the coordinate is located before the first element of the array. Coordinates
+/// beyond the last element are not checked, these are rejected when accessing the array.
+fn array_coordinate(
+    inverse_transform: &Transform,
+    axis_order: AxisOrder,
+    cell: &H3Cell,
+) -> Result<Option<[usize; 2]>, Error> {
+    let transformed = inverse_transform * cell.to_coordinate()?;
+    if transformed.x < 0.0 || transformed.y < 0.0 {
+        return Ok(None);
+    }
+    Ok(Some(match axis_order {
+        AxisOrder::XY => [
+            transformed.x.floor() as usize,
+            transformed.y.floor() as usize,
+        ],
+        AxisOrder::YX => [
+            transformed.y.floor() as usize,
+            transformed.x.floor() as usize,
+        ],
+    }))
+}
+
 /// convert a 2-d ndarray to h3
 pub struct H3Converter<'a, T>
 where
@@ -133,6 +187,8 @@ where
     nodata_value: &'a Option<T>,
     transform: &'a Transform,
     axis_order: AxisOrder,
+    progress_sender: Option<Sender<usize>>,
+    sort_output: bool,
 }
 
 impl<'a, T> H3Converter<'a, T>
@@ -150,9 +206,29 @@ where
             nodata_value,
             transform,
             axis_order,
+            progress_sender: None,
+            sort_output: false,
         }
     }
 
+    /// Report the progress of [`H3Converter::to_h3`] using the given channel.
+    ///
+    /// After each processed rect of the array, the number of rects completed so far is sent.
+    /// Send errors caused by a dropped receiver are ignored.
+    pub fn with_progress_sender(mut self, progress_sender: Sender<usize>) -> Self {
+        self.progress_sender = Some(progress_sender);
+        self
+    }
+
+    /// Sort the cells of each value returned by [`H3Converter::to_h3`].
+    ///
+    /// The conversion is done in parallel, so without sorting the order of the cells
+    /// may differ between runs. Sorting is disabled by default.
+    pub fn with_sorted_output(mut self, sort_output: bool) -> Self {
+        self.sort_output = sort_output;
+        self
+    }
+
     /// find the h3 resolution closest to the size of a pixel in an array
     pub fn nearest_h3_resolution(&self, search_mode: ResolutionSearchMode) -> Result<u8, Error> {
         nearest_h3_resolution(
@@ -215,25 +291,7 @@ where
     }
 
     fn rects_with_data_without_nodata(&self, rect_size: usize) -> Vec<Rect<f64>> {
-        // just create tiles covering the complete array
-        let x_size = self.arr.shape()[self.axis_order.x_axis()];
-        let y_size = self.arr.shape()[self.axis_order.y_axis()];
-        (0..((x_size as f64 / rect_size as f64).ceil() as usize))
-            .flat_map(move |r_x| {
-                (0..((y_size as f64 / rect_size as f64).ceil() as usize)).map(move |r_y| {
-                    Rect::new(
-                        Coord {
-                            x: (r_x * rect_size) as f64,
-                            y: (r_y * rect_size) as f64,
-                        },
-                        Coord {
-                            x: (min(x_size, (r_x + 1) * rect_size)) as f64,
-                            y: (min(y_size, (r_y + 1) * rect_size)) as f64,
-                        },
-                    )
-                })
-            })
-            .collect()
+        rects_covering_array(self.arr.shape(), self.axis_order, rect_size)
     }
 
     fn rects_with_data(&self, rect_size: usize) -> Vec<Rect<f64>> {
@@ -258,6 +316,7 @@ where
             n_rects
         );
 
+        let n_rects_completed = AtomicUsize::new(0);
         let chunk_h3_maps = rects
             .into_par_iter()
             .enumerate()
@@ -273,7 +332,7 @@ where
                 // the window in geographical coordinates
                 let window_box = self.transform * &array_window;
 
-                convert_array_window(
+                let converted = convert_array_window(
                     self.arr,
                     window_box,
                     &inverse_transform,
@@ -281,7 +340,14 @@ where
                     self.nodata_value,
                     h3_resolution,
                     compact,
-                )
+                );
+
+                if let Some(progress_sender) = self.progress_sender.as_ref() {
+                    let completed = n_rects_completed.fetch_add(1, Ordering::Relaxed) + 1;
+                    // the receiver may already be gone, which does not affect the conversion.
+                    let _ = progress_sender.send(completed);
+                }
+                converted
             })
             .collect::<Result<Vec<_>, _>>()?;
 
@@ -296,7 +362,30 @@ where
             }
         }
 
-        finalize_chunk_map(h3_map, compact)
+        let mut h3_map = finalize_chunk_map(h3_map, compact)?;
+        if self.sort_output {
+            h3_map
+                .par_iter_mut()
+                .for_each(|(_, compacted_vec)| compacted_vec.sort());
+        }
+        Ok(h3_map)
+    }
+
+    /// Variant of [`H3Converter::to_h3`] returning a map owning its keys, so it is not
+    /// bound to the lifetime of the array.
+    pub fn to_h3_owned(
+        &self,
+        h3_resolution: u8,
+        compact: bool,
+    ) -> Result<HashMap<T, CompactedCellVec>, Error>
+    where
+        T: Clone,
+    {
+        Ok(self
+            .to_h3(h3_resolution, compact)?
+            .into_iter()
+            .map(|(value, compacted_vec)| (value.clone(), compacted_vec))
+            .collect())
     }
 }
 
@@ -314,20 +403,9 @@ where
 {
     let mut chunk_h3_map = HashMap::<&T, CompactedCellVec>::default();
     for cell in window_box.to_h3_cells(h3_resolution)?.iter() {
-        // find the array element for the coordinate of the h3ron index
-        let arr_coord = {
-            let transformed = inverse_transform * cell.to_coordinate()?;
-
-            match axis_order {
-                AxisOrder::XY => [
-                    transformed.x.floor() as usize,
-                    transformed.y.floor() as usize,
-                ],
-                AxisOrder::YX => [
-                    transformed.y.floor() as usize,
-                    transformed.x.floor() as usize,
-                ],
-            }
+        let arr_coord = match array_coordinate(inverse_transform, axis_order, &cell)? {
+            Some(arr_coord) => arr_coord,
+            None => continue,
         };
         if let Some(value) = arr.get(arr_coord) {
             if let Some(nodata) = nodata_value {
@@ -369,9 +447,100 @@ where
         .collect()
 }
 
+/// Convert multiple bands of a raster to h3, collecting the values of all bands per cell.
+///
+/// All `arrays` must share the same shape and are located using the same `transform`. The
+/// returned `SmallVec` of each cell contains the values of the bands in the order of `arrays`.
+/// Up to `N` bands are stored inline without a separate allocation per cell.
+/// Cells are only omitted when all bands contain the `nodata_value`.
+pub fn to_h3_multiband<T, const N: usize>(
+    arrays: &[ArrayView2<T>],
+    nodata_value: &Option<T>,
+    transform: &Transform,
+    axis_order: AxisOrder,
+    h3_resolution: u8,
+) -> Result<HashMap<H3Cell, SmallVec<[T; N]>>, Error>
+where
+    T: Copy + PartialEq + Sync + Send,
+{
+    let shape = arrays.first().ok_or(Error::EmptyArray)?.shape();
+    if arrays.iter().any(|arr| arr.shape() != shape) {
+        return Err(Error::UnsupportedArrayShape);
+    }
+    let inverse_transform = transform.invert()?;
+
+    let rect_size = (shape[axis_order.x_axis()] / 10).clamp(10, 100);
+    let chunk_maps = rects_covering_array(shape, axis_order, rect_size)
+        .into_par_iter()
+        .map(|array_window| {
+            // the window in geographical coordinates
+            let window_box = transform * &array_window;
+
+            let mut chunk_map = HashMap::default();
+            for cell in window_box.to_h3_cells(h3_resolution)?.iter() {
+                let arr_coord = match array_coordinate(&inverse_transform, axis_order, &cell)? {
+                    Some(arr_coord) => arr_coord,
+                    None => continue,
+                };
+                let values = match arrays
+                    .iter()
+                    .map(|arr| arr.get(arr_coord).copied())
+                    .collect::<Option<SmallVec<_>>>()
+                {
+                    Some(values) => values,
+                    None => continue,
+                };
+                if let Some(nodata) = nodata_value {
+                    if values.iter().all(|value| value == nodata) {
+                        continue;
+                    }
+                }
+                chunk_map.insert(cell, values);
+            }
+            Ok(chunk_map)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let mut h3_map = HashMap::default();
+    for chunk_map in chunk_maps {
+        h3_map.extend(chunk_map);
+    }
+    Ok(h3_map)
+}
+
+/// Merge the converted cells of `other` into `target`.
+///
+/// This allows mosaicking the results of separately converted, adjacent rasters. Cells
+/// contained in both maps for the same value are only contained once in the result.
+/// The vecs of all values present in `other` get compacted when `compact` is set,
+/// otherwise they are just deduplicated.
+pub fn merge_converted<'a, T>(
+    target: &mut HashMap<&'a T, CompactedCellVec>,
+    other: HashMap<&'a T, CompactedCellVec>,
+    compact: bool,
+) -> Result<(), Error>
+where
+    T: Eq + Hash,
+{
+    for (value, mut compacted_vec) in other {
+        let target_vec = target.entry(value).or_insert_with(CompactedCellVec::new);
+        target_vec.append(&mut compacted_vec, false)?;
+        if compact {
+            target_vec.compact()?;
+        } else {
+            target_vec.dedup()?;
+        }
+        target_vec.shrink_to_fit();
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::array::find_boxes_containing_data;
+    use h3ron::collections::HashMap;
+    use h3ron::{collections::CompactedCellVec, H3Cell, Index, ToCoordinate};
+
+    use crate::array::{find_boxes_containing_data, merge_converted, to_h3_multiband};
     use crate::{AxisOrder, H3Converter, ResolutionSearchMode, Transform};
 
     #[test]
@@ -429,4 +598,197 @@ mod tests {
         assert!(cell_map.contains_key(&OrderedFloat(f32::NAN)));
         assert!(cell_map.contains_key(&OrderedFloat(1.0_f32)));
     }
+
+    #[test]
+    fn merge_overlapping() {
+        let center = H3Cell::from_coordinate((12.0, 45.0).into(), 8).unwrap();
+        let disk_a = center.grid_disk(2).unwrap();
+        let disk_b = center
+            .grid_disk(1)
+            .unwrap()
+            .iter()
+            .last()
+            .unwrap()
+            .grid_disk(2)
+            .unwrap();
+
+        let value = 1_u8;
+        let mut vec_a = CompactedCellVec::new();
+        vec_a.add_cells(disk_a.iter(), false).unwrap();
+        let mut vec_b = CompactedCellVec::new();
+        vec_b.add_cells(disk_b.iter(), false).unwrap();
+
+        let mut target = HashMap::default();
+        target.insert(&value, vec_a);
+        let mut other = HashMap::default();
+        other.insert(&value, vec_b);
+
+        merge_converted(&mut target, other, false).unwrap();
+
+        let merged = target.get(&value).unwrap();
+        let mut cells = merged
+            .iter_uncompacted_cells(8)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let num_cells = cells.len();
+        cells.sort_unstable();
+        cells.dedup();
+        assert_eq!(num_cells, cells.len());
+
+        let mut expected = disk_a.iter().chain(disk_b.iter()).collect::<Vec<_>>();
+        expected.sort_unstable();
+        expected.dedup();
+        assert!(expected.len() < disk_a.count() + disk_b.count());
+        assert_eq!(cells, expected);
+    }
+
+    #[test]
+    fn progress_messages() {
+        let arr = ndarray::Array2::<u8>::ones((30, 30));
+        let transform = Transform::from_gdal(&[11.0, 0.01, 0.0, 10.0, 0.0, -0.01]);
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        let view = arr.view();
+        let converter =
+            H3Converter::new(&view, &None, &transform, AxisOrder::YX).with_progress_sender(sender);
+        let h3_resolution = converter
+            .nearest_h3_resolution(ResolutionSearchMode::SmallerThanPixel)
+            .unwrap();
+        let n_rects = converter.rects_with_data(10).len();
+        let cell_map = converter.to_h3(h3_resolution, false).unwrap();
+        assert!(cell_map.contains_key(&1));
+
+        // dropping the converter closes the channel
+        drop(converter);
+        let mut messages = receiver.iter().collect::<Vec<_>>();
+        messages.sort_unstable();
+        assert!(n_rects > 1);
+        assert_eq!(messages, (1..=n_rects).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn sorted_output_is_reproducible() {
+        let arr = ndarray::Array2::from_shape_fn((40, 40), |(y, x)| ((y / 7) * 3 + x / 9) as u8);
+        let transform = Transform::from_gdal(&[11.0, 0.01, 0.0, 10.0, 0.0, -0.01]);
+        let view = arr.view();
+
+        let convert = || {
+            let converter =
+                H3Converter::new(&view, &None, &transform, AxisOrder::YX).with_sorted_output(true);
+            let h3_resolution = converter
+                .nearest_h3_resolution(ResolutionSearchMode::SmallerThanPixel)
+                .unwrap();
+            let mut values = converter
+                .to_h3(h3_resolution, true)
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>();
+            values.sort_unstable_by_key(|(value, _)| **value);
+
+            let mut bytes = Vec::new();
+            for (value, compacted_vec) in values {
+                bytes.push(*value);
+                for cell in compacted_vec.iter_compacted_cells() {
+                    bytes.extend(cell.h3index().to_le_bytes());
+                }
+            }
+            bytes
+        };
+
+        let bytes = convert();
+        assert!(!bytes.is_empty());
+        assert_eq!(bytes, convert());
+    }
+
+    #[test]
+    fn owned_map_outlives_array() {
+        let transform = Transform::from_gdal(&[11.0, 0.01, 0.0, 10.0, 0.0, -0.01]);
+        let convert = || {
+            let arr = array![[1_u8, 2], [1, 0]];
+            let view = arr.view();
+            let converter = H3Converter::new(&view, &Some(0), &transform, AxisOrder::YX);
+            let h3_resolution = converter
+                .nearest_h3_resolution(ResolutionSearchMode::SmallerThanPixel)
+                .unwrap();
+            converter.to_h3_owned(h3_resolution, false).unwrap()
+        };
+
+        let cell_map = convert();
+        assert_eq!(cell_map.len(), 2);
+        assert!(!cell_map.get(&1).unwrap().is_empty());
+        assert!(!cell_map.get(&2).unwrap().is_empty());
+        assert!(!cell_map.contains_key(&0));
+    }
+
+    #[test]
+    fn rotated_transform() {
+        let arr = ndarray::Array2::from_shape_fn((20, 20), |(y, x)| (y * 20 + x) as i32);
+        let rotation = 30.0_f64.to_radians();
+        let pixel_size = 0.01;
+        let transform = Transform::new(
+            pixel_size * rotation.cos(),
+            pixel_size * rotation.sin(),
+            11.0,
+            pixel_size * rotation.sin(),
+            -pixel_size * rotation.cos(),
+            10.0,
+        );
+        let inverse_transform = transform.invert().unwrap();
+
+        let view = arr.view();
+        let converter = H3Converter::new(&view, &None, &transform, AxisOrder::YX);
+        let h3_resolution = converter
+            .nearest_h3_resolution(ResolutionSearchMode::SmallerThanPixel)
+            .unwrap();
+        let cell_map = converter.to_h3(h3_resolution, false).unwrap();
+
+        // each pixel is covered by at least one cell
+        assert_eq!(cell_map.len(), arr.len());
+
+        for (value, compacted_vec) in cell_map {
+            for cell in compacted_vec.iter_uncompacted_cells(h3_resolution) {
+                let coord = &inverse_transform * cell.unwrap().to_coordinate().unwrap();
+                assert_eq!(
+                    arr[[coord.y.floor() as usize, coord.x.floor() as usize]],
+                    *value
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn multiband_pairs() {
+        let transform = Transform::from_gdal(&[11.0, 0.01, 0.0, 10.0, 0.0, -0.01]);
+        let band1 = array![[1_u8, 2], [3, 0]];
+        let band2 = array![[10_u8, 20], [30, 0]];
+        let views = [band1.view(), band2.view()];
+
+        let h3_resolution = H3Converter::new(&views[0], &None, &transform, AxisOrder::YX)
+            .nearest_h3_resolution(ResolutionSearchMode::SmallerThanPixel)
+            .unwrap();
+        let cell_map =
+            to_h3_multiband::<_, 2>(&views, &Some(0), &transform, AxisOrder::YX, h3_resolution)
+                .unwrap();
+
+        assert!(!cell_map.is_empty());
+        assert!(cell_map.values().all(|values| !values.spilled()));
+        let mut pairs = cell_map
+            .values()
+            .map(|values| values.to_vec())
+            .collect::<Vec<_>>();
+        pairs.sort_unstable();
+        pairs.dedup();
+        assert_eq!(pairs, vec![vec![1, 10], vec![2, 20], vec![3, 30]]);
+
+        // the bands must share the same shape
+        let band3 = array![[1_u8, 2, 3]];
+        assert!(to_h3_multiband::<_, 2>(
+            &[band1.view(), band3.view()],
+            &Some(0),
+            &transform,
+            AxisOrder::YX,
+            h3_resolution
+        )
+        .is_err());
+    }
 }
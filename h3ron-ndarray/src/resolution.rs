@@ -1,5 +1,6 @@
 use geo_types::{Coord, Rect};
 
+pub use h3ron::ResolutionSearchMode;
 use h3ron::{H3Cell, ToPolygon, H3_MAX_RESOLUTION, H3_MIN_RESOLUTION};
 
 use crate::{
@@ -9,15 +10,6 @@ use crate::{
     AxisOrder,
 };
 
-pub enum ResolutionSearchMode {
-    /// Chose the h3 resolution where the difference in the area of a pixel and the h3index is
-    /// as small as possible.
-    MinDiff,
-
-    /// Chose the h3 resolution where the area of the h3index is smaller than the area of a pixel.
-    SmallerThanPixel,
-}
-
 /// Find the h3 resolution closed to the size of a pixel in an array
 /// of the given shape with the given transform.
 pub fn nearest_h3_resolution(
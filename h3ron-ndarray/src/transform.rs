@@ -124,11 +124,30 @@ impl Mul<Coord<f64>> for &Transform {
 }
 
 /// apply the transformation to a rect
+///
+/// The result is the bounding rect of all four transformed corners, so it also covers
+/// the complete area of the input rect for transforms including rotation terms.
 impl Mul<&Rect<f64>> for &Transform {
     type Output = Rect<f64>;
 
     fn mul(self, rhs: &Rect<f64>) -> Self::Output {
-        Rect::new(self * rhs.min(), self * rhs.max())
+        let corners = [
+            self * rhs.min(),
+            self * rhs.max(),
+            self * Coord::from((rhs.min().x, rhs.max().y)),
+            self * Coord::from((rhs.max().x, rhs.min().y)),
+        ];
+        let (min, max) =
+            corners
+                .iter()
+                .skip(1)
+                .fold((corners[0], corners[0]), |(min, max), corner| {
+                    (
+                        Coord::from((min.x.min(corner.x), min.y.min(corner.y))),
+                        Coord::from((max.x.max(corner.x), max.y.max(corner.y))),
+                    )
+                });
+        Rect::new(min, max)
     }
 }
 
@@ -172,7 +191,7 @@ mod tests {
       NoData Value=0
      */
 
-    use geo_types::Coord;
+    use geo_types::{Coord, Rect};
 
     use crate::transform::Transform;
 
@@ -232,4 +251,34 @@ mod tests {
         ]);
         r_tiff_test_helper(&gt);
     }
+
+    #[test]
+    fn test_rotated_rect() {
+        let rotation = 30.0_f64.to_radians();
+        let gt = Transform::new(
+            rotation.cos(),
+            -rotation.sin(),
+            10.0,
+            rotation.sin(),
+            rotation.cos(),
+            20.0,
+        );
+        let rect = Rect::new(Coord::from((0.0, 0.0)), Coord::from((4.0, 2.0)));
+        let transformed = &gt * &rect;
+        for corner in [
+            rect.min(),
+            rect.max(),
+            Coord::from((0.0, 2.0)),
+            Coord::from((4.0, 0.0)),
+        ] {
+            let c = &gt * corner;
+            assert!(c.x >= transformed.min().x && c.x <= transformed.max().x);
+            assert!(c.y >= transformed.min().y && c.y <= transformed.max().y);
+        }
+        assert_relative_eq!(transformed.min().x, 10.0 - 2.0 * rotation.sin());
+        assert_relative_eq!(
+            transformed.max().y,
+            20.0 + 4.0 * rotation.sin() + 2.0 * rotation.cos()
+        );
+    }
 }
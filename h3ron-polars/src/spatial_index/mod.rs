@@ -34,8 +34,10 @@ use h3ron::to_geo::ToLine;
 use h3ron::{H3Cell, H3DirectedEdge, ToCoordinate, ToPolygon};
 use polars::export::arrow::array::BooleanArray;
 use polars::export::arrow::bitmap::{Bitmap, MutableBitmap};
+use polars::export::rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use polars::prelude::BooleanChunked;
 use polars_core::prelude::{FromData, TakeRandom, UInt64Chunked};
+use polars_core::POOL;
 
 #[cfg(feature = "si_kdtree")]
 pub use crate::spatial_index::kdtree::*;
@@ -72,6 +74,27 @@ pub trait SpatialIndex<IX: IndexValue, Kind: SIKind> {
         )
     }
 
+    /// Same as [SpatialIndex::envelopes_intersect], but for many `rects` at once.
+    ///
+    /// The queries are executed in parallel. The returned masks are in the order of `rects`.
+    fn envelopes_intersect_many(&self, rects: &[Rect]) -> Vec<BooleanChunked>
+    where
+        Self: Sync,
+    {
+        let validities = self.h3indexchunked().validity_bitmap();
+        let masks: Vec<Bitmap> = POOL.install(|| {
+            // Ordering is preserved. see https://github.com/rayon-rs/rayon/issues/551
+            rects
+                .par_iter()
+                .map(|rect| self.envelopes_intersect_impl(rect).into())
+                .collect()
+        });
+        masks
+            .into_iter()
+            .map(|mask| finish_mask_with_validities(mask, validities.clone()))
+            .collect()
+    }
+
     /// The envelope of the indexed elements is with `distance` of the given [Coord] `coord`.
     fn envelopes_within_distance(&self, coord: Coord, distance: f64) -> BooleanChunked;
 }
@@ -180,8 +203,11 @@ pub(crate) fn negative_mask(ca: &UInt64Chunked) -> MutableBitmap {
 }
 
 pub(crate) fn finish_mask<IX: IndexValue>(mask: Bitmap, ic: &IndexChunked<IX>) -> BooleanChunked {
-    let validites = ic.validity_bitmap();
-    let bool_arr = BooleanArray::from_data_default(mask, Some(validites));
+    finish_mask_with_validities(mask, ic.validity_bitmap())
+}
+
+fn finish_mask_with_validities(mask: Bitmap, validities: Bitmap) -> BooleanChunked {
+    let bool_arr = BooleanArray::from_data_default(mask, Some(validities));
     BooleanChunked::from(bool_arr)
 }
 
@@ -91,6 +91,116 @@ where
     }
 }
 
+/// Size of the header of the serialized index: number of items and node size.
+const SERIALIZED_HEADER_SIZE: usize = 2 * 8;
+
+/// Size of a serialized item: position in the chunked array and the four extents of the envelope.
+const SERIALIZED_ITEM_SIZE: usize = 5 * 8;
+
+impl<IX: IndexValue> PackedHilbertRTreeIndex<IX> {
+    /// Serialize the built index to bytes.
+    ///
+    /// The envelopes of the indexed elements are stored together with their positions in the
+    /// chunked array, so the expensive calculation of the envelopes can be skipped when
+    /// restoring the index using [`PackedHilbertRTreeIndex::deserialize`]. The indexed
+    /// chunked array itself is not part of the serialized bytes.
+    pub fn serialize(&self) -> Vec<u8> {
+        let (node_size, boxes) = if let Some(index) = self.index.as_ref() {
+            // restore the order in which the boxes were added to be able to
+            // re-create an identical tree.
+            let mut boxes = vec![None; index.count()];
+            for (aabb, item_index) in index.item_boxes().iter().zip(index.item_indices()) {
+                boxes[*item_index] = Some(aabb);
+            }
+            (index.node_size(), boxes.into_iter().flatten().collect())
+        } else {
+            (0, vec![])
+        };
+
+        let mut bytes =
+            Vec::with_capacity(SERIALIZED_HEADER_SIZE + boxes.len() * SERIALIZED_ITEM_SIZE);
+        bytes.extend_from_slice(&(boxes.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&(node_size as u64).to_le_bytes());
+        for (aabb, position) in boxes.iter().zip(self.positions_in_chunked_array.iter()) {
+            bytes.extend_from_slice(&(*position as u64).to_le_bytes());
+            for value in [aabb.min_x, aabb.min_y, aabb.max_x, aabb.max_y] {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Restore an index serialized using [`PackedHilbertRTreeIndex::serialize`].
+    ///
+    /// `chunked` must be the same array the index was originally built from.
+    pub fn deserialize(bytes: &[u8], chunked: IndexChunked<IX>) -> Result<Self, Error> {
+        let mut words = bytes.chunks_exact(8).map(|word| {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(word);
+            buf
+        });
+        let mut next_u64 = || {
+            words
+                .next()
+                .map(u64::from_le_bytes)
+                .ok_or_else(|| Error::SpatialIndex("serialized index is truncated".to_string()))
+        };
+
+        let num_items = next_u64()? as usize;
+        let node_size = next_u64()? as usize;
+        let expected_len = num_items
+            .checked_mul(SERIALIZED_ITEM_SIZE)
+            .and_then(|items_len| items_len.checked_add(SERIALIZED_HEADER_SIZE));
+        if expected_len != Some(bytes.len()) {
+            return Err(Error::SpatialIndex(
+                "serialized index has an unexpected length".to_string(),
+            ));
+        }
+
+        let mut positions_in_chunked_array = Vec::with_capacity(num_items);
+        let index = if num_items > 0 {
+            let mut builder = StaticAABB2DIndexBuilder::new_with_node_size(num_items, node_size);
+            for _ in 0..num_items {
+                let position = next_u64()? as usize;
+                if position >= chunked.len() {
+                    return Err(Error::SpatialIndex(
+                        "serialized index does not match the chunked array".to_string(),
+                    ));
+                }
+                positions_in_chunked_array.push(position);
+
+                let min_x = f64::from_bits(next_u64()?);
+                let min_y = f64::from_bits(next_u64()?);
+                let max_x = f64::from_bits(next_u64()?);
+                let max_y = f64::from_bits(next_u64()?);
+                if !([min_x, min_y, max_x, max_y].iter().all(|v| v.is_finite())
+                    && min_x <= max_x
+                    && min_y <= max_y)
+                {
+                    return Err(Error::SpatialIndex(
+                        "serialized index contains an invalid box".to_string(),
+                    ));
+                }
+                builder.add(min_x, min_y, max_x, max_y);
+            }
+            Some(
+                builder
+                    .build()
+                    .map_err(|e| Error::SpatialIndex(e.to_string()))?,
+            )
+        } else {
+            None
+        };
+
+        Ok(Self {
+            index,
+            index_phantom: PhantomData::<IX>,
+            chunked_array: chunked.chunked_array.clone(),
+            positions_in_chunked_array: positions_in_chunked_array.into_boxed_slice(),
+        })
+    }
+}
+
 impl<IX: IndexValue> SpatialIndex<IX, RectSIKind> for PackedHilbertRTreeIndex<IX> {
     fn h3indexchunked(&self) -> IndexChunked<IX> {
         self.chunked_array.h3indexchunked()
@@ -170,4 +280,112 @@ mod tests {
         cc.packed_hilbert_rtree_index().unwrap()
     }
     crate::spatial_index::tests::impl_std_tests!(build_index);
+
+    #[test]
+    fn serialize_roundtrip() {
+        let ca = UInt64Chunked::new_from_indexes(
+            "",
+            H3Cell::from_coordinate((12.3, 45.6).into(), 6)
+                .unwrap()
+                .grid_disk(8)
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+        );
+        let idx = build_index(&ca.h3cell());
+        let bytes = idx.serialize();
+
+        let restored = PackedHilbertRTreeIndex::<H3Cell>::deserialize(&bytes, ca.h3cell()).unwrap();
+
+        let query_rect = Rect::new((12.2, 45.5), (12.5, 45.8));
+        let found = idx
+            .envelopes_intersect(&query_rect)
+            .into_iter()
+            .collect::<Vec<_>>();
+        assert!(found.contains(&Some(true)));
+        assert!(found.contains(&Some(false)));
+        assert_eq!(
+            found,
+            restored
+                .envelopes_intersect(&query_rect)
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            idx.envelopes_within_distance((12.3, 45.6).into(), 0.1)
+                .into_iter()
+                .collect::<Vec<_>>(),
+            restored
+                .envelopes_within_distance((12.3, 45.6).into(), 0.1)
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(bytes, restored.serialize());
+
+        assert!(PackedHilbertRTreeIndex::<H3Cell>::deserialize(
+            &bytes[..bytes.len() - 1],
+            ca.h3cell()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn serialize_empty_roundtrip() {
+        let values: Vec<u64> = vec![];
+        let ca = UInt64Chunked::new("", values);
+        let bytes = build_index(&ca.h3cell()).serialize();
+        let restored = PackedHilbertRTreeIndex::<H3Cell>::deserialize(&bytes, ca.h3cell()).unwrap();
+        assert!(restored.index.is_none());
+    }
+
+    #[test]
+    fn deserialize_forged_header() {
+        let values: Vec<u64> = vec![];
+        let ca = UInt64Chunked::new("", values);
+
+        // num_items chosen so the expected length wraps around to the length of the header
+        for num_items in [1_u64 << 61, u64::MAX] {
+            let mut bytes = num_items.to_le_bytes().to_vec();
+            bytes.extend_from_slice(&16_u64.to_le_bytes());
+            assert!(PackedHilbertRTreeIndex::<H3Cell>::deserialize(&bytes, ca.h3cell()).is_err());
+        }
+
+        // shorter than the header
+        assert!(PackedHilbertRTreeIndex::<H3Cell>::deserialize(&[0u8; 4], ca.h3cell()).is_err());
+    }
+
+    #[test]
+    fn deserialize_forged_box() {
+        let ca = UInt64Chunked::new_from_indexes(
+            "",
+            vec![H3Cell::from_coordinate((12.3, 45.6).into(), 6).unwrap()],
+        );
+        let bytes = build_index(&ca.h3cell()).serialize();
+        assert!(PackedHilbertRTreeIndex::<H3Cell>::deserialize(&bytes, ca.h3cell()).is_ok());
+
+        // the box follows the header and the position of the item
+        let box_offset = 3 * 8;
+        let read_f64 = |bytes: &[u8], i: usize| {
+            let offset = box_offset + i * 8;
+            f64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+        };
+        let write_f64 = |bytes: &mut Vec<u8>, i: usize, value: f64| {
+            let offset = box_offset + i * 8;
+            bytes[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+        };
+
+        // inverted box
+        let mut forged = bytes.clone();
+        let (min_x, max_x) = (read_f64(&bytes, 0), read_f64(&bytes, 2));
+        write_f64(&mut forged, 0, max_x + 1.0);
+        write_f64(&mut forged, 2, min_x);
+        assert!(PackedHilbertRTreeIndex::<H3Cell>::deserialize(&forged, ca.h3cell()).is_err());
+
+        // non-finite values
+        for value in [f64::NAN, f64::INFINITY] {
+            let mut forged = bytes.clone();
+            write_f64(&mut forged, 1, value);
+            assert!(PackedHilbertRTreeIndex::<H3Cell>::deserialize(&forged, ca.h3cell()).is_err());
+        }
+    }
 }
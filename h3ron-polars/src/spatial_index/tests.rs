@@ -71,6 +71,28 @@ macro_rules! impl_std_tests {
             assert_eq!(mask.get(2), Some(false));
             assert_eq!(mask.get(3), None);
         }
+
+        #[test]
+        fn cell_envelopes_intersect_many() {
+            let ca = build_cell_ca();
+            let idx = $mk_index(&ca.h3cell());
+            let rects = vec![
+                Rect::new((40.0, 40.0), (50.0, 50.0)),
+                Rect::new((-70.0, -70.0), (-50.0, -50.0)),
+                Rect::new((0.0, 0.0), (1.0, 1.0)),
+                Rect::new((-180.0, -90.0), (180.0, 90.0)),
+            ];
+            let masks = idx.envelopes_intersect_many(&rects);
+
+            assert_eq!(masks.len(), rects.len());
+            for (rect, mask) in rects.iter().zip(masks.iter()) {
+                let expected = idx.envelopes_intersect(rect);
+                assert_eq!(
+                    mask.into_iter().collect::<Vec<_>>(),
+                    expected.into_iter().collect::<Vec<_>>()
+                );
+            }
+        }
     }
 }
 
@@ -4,7 +4,7 @@ use std::time::Duration;
 use criterion::{criterion_group, criterion_main, Criterion};
 use geo_types::Coord;
 
-use h3ron::collections::{H3CellMap, H3Treemap, RandomState};
+use h3ron::collections::{h3cellmap_from_sorted, H3CellMap, H3Treemap, RandomState};
 use h3ron::H3Cell;
 
 fn criterion_benchmark(c: &mut Criterion) {
@@ -27,6 +27,16 @@ fn criterion_benchmark(c: &mut Criterion) {
             });
         },
     );
+    group.bench_function(
+        format!("h3cellmap_from_sorted (n={})", cells.len()),
+        |bencher| {
+            let mut sorted_cells = cells.clone();
+            sorted_cells.sort_unstable();
+            bencher.iter(|| {
+                h3cellmap_from_sorted(sorted_cells.iter().map(|cell| (*cell, value)));
+            });
+        },
+    );
     group.bench_function(
         format!("H3Treemap::from_iter_with_sort (n={})", cells.len()),
         |bencher| {
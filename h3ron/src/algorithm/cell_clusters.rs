@@ -3,7 +3,7 @@ use crate::iter::GridDiskBuilder;
 use crate::{Error, H3Cell};
 use ahash::RandomState;
 use hashbrown::hash_map::Entry;
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 use std::cmp::Ordering;
 
 /// find clusters of neighboring cells
@@ -60,6 +60,92 @@ impl<Value> CellAndValue<Value> for (H3Cell, Value) {
     }
 }
 
+/// Result of [`dbscan_clusters`]
+#[derive(Debug, Clone, Default)]
+pub struct DbscanClusters {
+    /// the found clusters
+    pub clusters: Vec<Vec<H3Cell>>,
+
+    /// cells not belonging to any cluster
+    pub noise: Vec<H3Cell>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DbscanLabel {
+    Unvisited,
+    Noise,
+    Cluster(usize),
+}
+
+/// find clusters of cells using the density-based DBSCAN algorithm.
+///
+/// `eps_k` is the grid distance defining the neighborhood of a cell. Cells having at least
+/// `min_points` cells - including themselves - within their neighborhood form the core of a
+/// cluster. Cells which are neither core cells nor within the neighborhood of a core cell
+/// are classified as noise.
+///
+/// Requires the `indexmap` feature.
+pub fn dbscan_clusters<I>(cells: I, eps_k: u32, min_points: usize) -> Result<DbscanClusters, Error>
+where
+    I: IntoIterator<Item = H3Cell>,
+{
+    let cells: IndexSet<_, RandomState> = cells.into_iter().collect();
+    let mut labels = vec![DbscanLabel::Unvisited; cells.len()];
+    let mut disk_builder = GridDiskBuilder::create(0, eps_k)?;
+    let mut neighbors_of = |pos: usize| -> Result<Vec<usize>, Error> {
+        Ok(disk_builder
+            .build_grid_disk(&cells[pos])?
+            .filter_map(|(cell, _)| cells.get_index_of(&cell))
+            .collect())
+    };
+
+    let mut num_clusters = 0;
+    for pos in 0..cells.len() {
+        if labels[pos] != DbscanLabel::Unvisited {
+            continue;
+        }
+        let neighbors = neighbors_of(pos)?;
+        if neighbors.len() < min_points {
+            labels[pos] = DbscanLabel::Noise;
+            continue;
+        }
+
+        let cluster_id = num_clusters;
+        num_clusters += 1;
+        labels[pos] = DbscanLabel::Cluster(cluster_id);
+
+        let mut queue = neighbors;
+        while let Some(neighbor_pos) = queue.pop() {
+            match labels[neighbor_pos] {
+                DbscanLabel::Noise => {
+                    // border cell of the cluster
+                    labels[neighbor_pos] = DbscanLabel::Cluster(cluster_id);
+                }
+                DbscanLabel::Unvisited => {
+                    labels[neighbor_pos] = DbscanLabel::Cluster(cluster_id);
+                    let neighbor_neighbors = neighbors_of(neighbor_pos)?;
+                    if neighbor_neighbors.len() >= min_points {
+                        queue.extend(neighbor_neighbors);
+                    }
+                }
+                DbscanLabel::Cluster(_) => {}
+            }
+        }
+    }
+
+    let mut result = DbscanClusters {
+        clusters: vec![Vec::new(); num_clusters],
+        noise: Vec::new(),
+    };
+    for (cell, label) in cells.into_iter().zip(labels) {
+        match label {
+            DbscanLabel::Cluster(cluster_id) => result.clusters[cluster_id].push(cell),
+            DbscanLabel::Noise | DbscanLabel::Unvisited => result.noise.push(cell),
+        }
+    }
+    Ok(result)
+}
+
 fn find_cell_clusters_eq_value_impl<CellValueIter, CV, Value>(
     cell_value_iter: CellValueIter,
 ) -> Result<HashMap<usize, (Vec<H3Cell>, Value)>, Error>
@@ -117,7 +203,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::algorithm::find_cell_clusters;
+    use crate::algorithm::{dbscan_clusters, find_cell_clusters};
     use crate::H3Cell;
 
     #[test]
@@ -148,4 +234,27 @@ mod tests {
         assert!(cluster2 == disk1 || cluster2 == disk2);
         assert_ne!(cluster1, cluster2);
     }
+
+    #[test]
+    fn dbscan_clusters_blob_and_noise() {
+        let mut blob: Vec<_> = H3Cell::from_coordinate((12.2, 14.5).into(), 8)
+            .unwrap()
+            .grid_disk(2)
+            .unwrap()
+            .iter()
+            .collect();
+        blob.sort_unstable();
+        let mut noise: Vec<_> = [(13.2, 14.5), (12.2, 15.5), (11.2, 13.5)]
+            .into_iter()
+            .map(|c| H3Cell::from_coordinate(c.into(), 8).unwrap())
+            .collect();
+        noise.sort_unstable();
+
+        let mut result = dbscan_clusters(blob.iter().chain(noise.iter()).copied(), 1, 4).unwrap();
+        assert_eq!(result.clusters.len(), 1);
+        result.clusters[0].sort_unstable();
+        assert_eq!(result.clusters[0], blob);
+        result.noise.sort_unstable();
+        assert_eq!(result.noise, noise);
+    }
 }
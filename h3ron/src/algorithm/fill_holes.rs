@@ -0,0 +1,163 @@
+use std::collections::VecDeque;
+
+use crate::collections::{H3CellSet, H3Treemap};
+use crate::{Error, H3Cell, Index};
+
+/// Fill the holes enclosed by `cells`.
+///
+/// Holes are connected regions of cells not contained in `cells` which are completely
+/// surrounded by `cells`. All holes consisting of up to `max_hole_cells` cells get added to the
+/// returned set. Larger holes and the area outside of `cells` are left unchanged.
+///
+/// All cells must be of the same resolution, otherwise [`Error::ResMismatch`] is returned.
+///
+/// Requires the `roaring` feature.
+pub fn fill_holes<I>(cells: I, max_hole_cells: usize) -> Result<H3Treemap<H3Cell>, Error>
+where
+    I: IntoIterator<Item = H3Cell>,
+{
+    let mut filled: H3Treemap<H3Cell> = H3Treemap::default();
+    let mut h3_resolution = None;
+    for cell in cells {
+        cell.validate()?;
+        if *h3_resolution.get_or_insert(cell.resolution()) != cell.resolution() {
+            return Err(Error::ResMismatch);
+        }
+        filled.insert(cell);
+    }
+
+    // candidates for holes are all non-member cells adjacent to the members
+    let mut candidates = Vec::new();
+    for cell in filled.iter() {
+        for neighbor in cell
+            .grid_ring_unsafe(1)
+            .or_else(|_| cell.grid_disk(1))?
+            .iter()
+        {
+            if !filled.contains(&neighbor) {
+                candidates.push(neighbor);
+            }
+        }
+    }
+
+    // cells known to be connected to a region exceeding `max_hole_cells`
+    let mut outside = H3CellSet::default();
+    for candidate in candidates {
+        if filled.contains(&candidate) || outside.contains(&candidate) {
+            continue;
+        }
+
+        // explore the region of non-member cells connected to the candidate, stopping
+        // as soon as the region is known to be larger than `max_hole_cells`.
+        let mut region = H3CellSet::default();
+        region.insert(candidate);
+        let mut queue = VecDeque::from([candidate]);
+        let mut is_hole = region.len() <= max_hole_cells;
+        while is_hole {
+            let Some(cell) = queue.pop_front() else {
+                break;
+            };
+            for neighbor in cell.grid_disk(1)?.iter() {
+                if filled.contains(&neighbor) || region.contains(&neighbor) {
+                    continue;
+                }
+                if outside.contains(&neighbor) {
+                    is_hole = false;
+                    break;
+                }
+                region.insert(neighbor);
+                if region.len() > max_hole_cells {
+                    is_hole = false;
+                    break;
+                }
+                queue.push_back(neighbor);
+            }
+        }
+
+        if is_hole {
+            for cell in region {
+                filled.insert(cell);
+            }
+        } else {
+            outside.extend(region);
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::Coord;
+
+    use crate::H3Cell;
+
+    use super::fill_holes;
+
+    fn center_cell() -> H3Cell {
+        H3Cell::from_coordinate(Coord::from((12.3, 45.6)), 8).unwrap()
+    }
+
+    #[test]
+    fn fill_single_cell_hole() {
+        let center = center_cell();
+        let ring = center.grid_ring_unsafe(1).unwrap();
+
+        let filled = fill_holes(ring.iter(), 1).unwrap();
+        assert_eq!(filled.len(), 7);
+        assert!(filled.contains(&center));
+    }
+
+    #[test]
+    fn max_hole_cells_zero_and_one() {
+        let center = center_cell();
+        let ring = center.grid_ring_unsafe(1).unwrap();
+
+        let unchanged = fill_holes(ring.iter(), 0).unwrap();
+        assert_eq!(unchanged.len(), 6);
+        assert!(!unchanged.contains(&center));
+
+        let filled = fill_holes(ring.iter(), 1).unwrap();
+        assert_eq!(filled.len(), 7);
+
+        // a hole of two cells exceeds the limit of one cell
+        let neighbor = ring.first().unwrap();
+        let two_cell_hole = center
+            .grid_disk(2)
+            .unwrap()
+            .iter()
+            .filter(|cell| {
+                *cell != center
+                    && *cell != neighbor
+                    && (cell.grid_distance_to(center).unwrap() == 1
+                        || cell.grid_distance_to(neighbor).unwrap() == 1)
+            })
+            .collect::<Vec<_>>();
+        let unchanged = fill_holes(two_cell_hole.iter().copied(), 1).unwrap();
+        assert!(!unchanged.contains(&center));
+        assert!(!unchanged.contains(&neighbor));
+
+        let filled = fill_holes(two_cell_hole.iter().copied(), 2).unwrap();
+        assert!(filled.contains(&center));
+        assert!(filled.contains(&neighbor));
+    }
+
+    #[test]
+    fn keep_holes_exceeding_threshold() {
+        let center = center_cell();
+        let ring = center.grid_ring_unsafe(2).unwrap();
+
+        let unchanged = fill_holes(ring.iter(), 6).unwrap();
+        assert_eq!(unchanged.len(), 12);
+        assert!(!unchanged.contains(&center));
+
+        let filled = fill_holes(ring.iter(), 7).unwrap();
+        assert_eq!(filled.len(), 19);
+        assert!(filled.contains(&center));
+    }
+
+    #[test]
+    fn mixed_resolutions() {
+        let center = center_cell();
+        assert!(fill_holes([center, center.get_parent(5).unwrap()], 1).is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use crate::collections::H3CellMap;
+use crate::{Error, ToCoordinate};
+
+/// mean earth radius in meters, as used by the haversine formula
+const MEAN_EARTH_RADIUS_M: f64 = 6_371_008.8;
+
+/// Estimate the gradient of the scalar `values` at each cell.
+///
+/// The gradient is determined by fitting a plane through the value of the cell and the values
+/// of its direct neighbors using least-squares. The offsets between the centroids of the cells
+/// are projected to meters in a local east/north plane, so the returned gradients are tuples
+/// of `(east, north)` components in units of value per meter.
+///
+/// Cells with less than two neighbors contained in `values` or with neighbors all located
+/// on a single line get a zero gradient.
+pub fn gradient_field(values: &H3CellMap<f64>) -> Result<H3CellMap<(f64, f64)>, Error> {
+    let mut gradients = H3CellMap::with_capacity_and_hasher(values.len(), Default::default());
+    for (cell, value) in values {
+        let centroid = cell.to_coordinate()?;
+        let meters_per_degree_lat = MEAN_EARTH_RADIUS_M.to_radians();
+        let meters_per_degree_lng = meters_per_degree_lat * centroid.y.to_radians().cos();
+
+        // sums for the normal equations of the least-squares fit
+        let (mut sxx, mut sxy, mut syy, mut sxv, mut syv) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        let mut num_neighbors = 0_usize;
+        for neighbor in cell.grid_disk(1)?.iter() {
+            let neighbor_value = match values.get(&neighbor) {
+                Some(neighbor_value) if neighbor != *cell => neighbor_value,
+                _ => continue,
+            };
+            let neighbor_centroid = neighbor.to_coordinate()?;
+
+            // normalize to the shorter way around the globe
+            let mut d_lng = neighbor_centroid.x - centroid.x;
+            if d_lng > 180.0 {
+                d_lng -= 360.0;
+            } else if d_lng < -180.0 {
+                d_lng += 360.0;
+            }
+
+            let dx = d_lng * meters_per_degree_lng;
+            let dy = (neighbor_centroid.y - centroid.y) * meters_per_degree_lat;
+            let dv = neighbor_value - value;
+            sxx += dx * dx;
+            sxy += dx * dy;
+            syy += dy * dy;
+            sxv += dx * dv;
+            syv += dy * dv;
+            num_neighbors += 1;
+        }
+
+        let determinant = sxx.mul_add(syy, -(sxy * sxy));
+        let gradient = if num_neighbors < 2 || determinant.abs() <= f64::EPSILON * sxx * syy {
+            (0.0, 0.0)
+        } else {
+            (
+                syy.mul_add(sxv, -(sxy * syv)) / determinant,
+                sxx.mul_add(syv, -(sxy * sxv)) / determinant,
+            )
+        };
+        gradients.insert(*cell, gradient);
+    }
+    Ok(gradients)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::H3CellMap;
+    use crate::{H3Cell, ToCoordinate};
+
+    use super::gradient_field;
+
+    #[test]
+    fn linear_ramp() {
+        let center = H3Cell::from_coordinate((12.3, 45.6).into(), 8).unwrap();
+
+        // value increases towards the east
+        let values: H3CellMap<f64> = center
+            .grid_disk(4)
+            .unwrap()
+            .iter()
+            .map(|cell| (cell, cell.to_coordinate().unwrap().x * 1000.0))
+            .collect();
+
+        let gradients = gradient_field(&values).unwrap();
+        assert_eq!(gradients.len(), values.len());
+        for (east, north) in gradients.values() {
+            assert!(*east > 0.0);
+            let direction = north.atan2(*east).to_degrees();
+            assert!(direction.abs() < 1.0, "direction: {}", direction);
+        }
+    }
+
+    #[test]
+    fn isolated_cell() {
+        let cell = H3Cell::from_coordinate((12.3, 45.6).into(), 8).unwrap();
+        let mut values = H3CellMap::default();
+        values.insert(cell, 4.0);
+        values.insert(cell.grid_ring_unsafe(1).unwrap().first().unwrap(), 5.0);
+
+        let gradients = gradient_field(&values).unwrap();
+        assert_eq!(gradients.len(), 2);
+        assert!(gradients.values().all(|gradient| *gradient == (0.0, 0.0)));
+    }
+}
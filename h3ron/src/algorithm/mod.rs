@@ -1,7 +1,15 @@
 #[cfg(feature = "indexmap")]
 pub mod cell_clusters;
+#[cfg(feature = "roaring")]
+pub mod fill_holes;
+pub mod gradient;
+pub mod nearest_seed;
 pub mod smoothen;
 
 #[cfg(feature = "indexmap")]
 pub use cell_clusters::*;
+#[cfg(feature = "roaring")]
+pub use fill_holes::*;
+pub use gradient::*;
+pub use nearest_seed::*;
 pub use smoothen::*;
@@ -0,0 +1,109 @@
+use crate::collections::{H3CellMap, H3CellSet};
+use crate::{Error, H3Cell, Index};
+
+/// Label each cell of `region` with the index of its nearest cell in `seeds`.
+///
+/// The distances are determined using a breadth-first search starting at all seeds at the
+/// same time, which only traverses the cells of the `region`. For compact regions this
+/// corresponds to the grid distance. Cells of the region which can not be reached from any
+/// seed are not contained in the returned map. When multiple seeds have the same distance
+/// to a cell, the seed with the lowest index is chosen.
+///
+/// All cells must be of the same resolution, otherwise [`Error::ResMismatch`] is returned.
+pub fn nearest_seed_labels(region: &[H3Cell], seeds: &[H3Cell]) -> Result<H3CellMap<usize>, Error> {
+    let mut h3_resolution = None;
+    for cell in region.iter().chain(seeds.iter()) {
+        cell.validate()?;
+        if *h3_resolution.get_or_insert(cell.resolution()) != cell.resolution() {
+            return Err(Error::ResMismatch);
+        }
+    }
+    let region: H3CellSet = region.iter().copied().collect();
+
+    let mut labels = H3CellMap::with_capacity_and_hasher(region.len(), Default::default());
+    let mut frontier: H3CellMap<usize> = H3CellMap::default();
+    for (seed_i, seed) in seeds.iter().enumerate() {
+        frontier.entry(*seed).or_insert(seed_i);
+    }
+
+    while !frontier.is_empty() {
+        for (cell, label) in frontier.iter() {
+            if region.contains(cell) {
+                labels.insert(*cell, *label);
+            }
+        }
+
+        // cells reached from multiple cells of the frontier get the lowest label
+        let mut next_frontier: H3CellMap<usize> = H3CellMap::default();
+        for (cell, label) in frontier.iter() {
+            for neighbor in cell.grid_disk(1)?.iter() {
+                if !region.contains(&neighbor)
+                    || labels.contains_key(&neighbor)
+                    || frontier.contains_key(&neighbor)
+                {
+                    continue;
+                }
+                next_frontier
+                    .entry(neighbor)
+                    .and_modify(|next_label| *next_label = (*next_label).min(*label))
+                    .or_insert(*label);
+            }
+        }
+        frontier = next_frontier;
+    }
+    Ok(labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::Coord;
+
+    use crate::H3Cell;
+
+    use super::nearest_seed_labels;
+
+    #[test]
+    fn two_seeds() {
+        let center = H3Cell::from_coordinate(Coord::from((12.3, 45.6)), 8).unwrap();
+        let region = center.grid_disk(6).unwrap().iter().collect::<Vec<_>>();
+        let seed_a = center.grid_ring_unsafe(3).unwrap().first().unwrap();
+        let seed_b = region
+            .iter()
+            .copied()
+            .find(|cell| {
+                seed_a.grid_distance_to(*cell).unwrap() == 6
+                    && center.grid_distance_to(*cell).unwrap() == 3
+            })
+            .unwrap();
+
+        let labels = nearest_seed_labels(&region, &[seed_a, seed_b]).unwrap();
+        assert_eq!(labels.len(), region.len());
+        assert_eq!(labels[&seed_a], 0);
+        assert_eq!(labels[&seed_b], 1);
+
+        for cell in region.iter() {
+            let distance_a = seed_a.grid_distance_to(*cell).unwrap();
+            let distance_b = seed_b.grid_distance_to(*cell).unwrap();
+            let expected = if distance_b < distance_a { 1 } else { 0 };
+            assert_eq!(labels[cell], expected);
+        }
+
+        // the center is on the dividing line and goes to the seed with the lower index
+        assert_eq!(labels[&center], 0);
+        assert_eq!(
+            nearest_seed_labels(&region, &[seed_b, seed_a]).unwrap()[&center],
+            0
+        );
+    }
+
+    #[test]
+    fn unreachable_cells() {
+        let center = H3Cell::from_coordinate(Coord::from((12.3, 45.6)), 8).unwrap();
+        let far_away = H3Cell::from_coordinate(Coord::from((40.0, 10.0)), 8).unwrap();
+        let labels = nearest_seed_labels(&[center, far_away], &[center]).unwrap();
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[&center], 0);
+
+        assert!(nearest_seed_labels(&[center], &[center.get_parent(5).unwrap()]).is_err());
+    }
+}
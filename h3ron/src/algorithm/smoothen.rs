@@ -2,7 +2,7 @@ use std::cmp::min;
 
 use geo::algorithm::area::Area;
 use geo::algorithm::simplify_vw::SimplifyVw;
-use geo_types::{Coord, LineString, Polygon, Triangle};
+use geo_types::{Coord, LineString, MultiPolygon, Polygon, Triangle};
 
 fn is_closed(ls: &[Coord<f64>]) -> bool {
     if ls.len() < 2 {
@@ -15,6 +15,18 @@ fn is_closed(ls: &[Coord<f64>]) -> bool {
 /// Smoothen a linestring to remove some of the artifacts
 /// of the h3indexes left after creating a h3 linkedpolygon.
 pub(crate) fn smoothen_h3_coordinates(in_coords: &[Coord<f64>]) -> Vec<Coord<f64>> {
+    if in_coords.len() >= 3 {
+        // the area covered by three point must be less than the triangle of three points of a hexagon
+        let hexagon_corner_area =
+            Triangle::from([in_coords[0], in_coords[1], in_coords[2]]).unsigned_area();
+        smoothen_coordinates(in_coords, hexagon_corner_area * 0.75)
+    } else {
+        in_coords.to_vec()
+    }
+}
+
+/// Smoothen a linestring and remove the vertices of corners with an area below `simplify_area`.
+fn smoothen_coordinates(in_coords: &[Coord<f64>], simplify_area: f64) -> Vec<Coord<f64>> {
     let closed = is_closed(in_coords);
     let mut out = Vec::with_capacity(in_coords.len() + if closed { 2 } else { 0 });
     if in_coords.len() >= 3 {
@@ -51,17 +63,62 @@ pub(crate) fn smoothen_h3_coordinates(in_coords: &[Coord<f64>]) -> Vec<Coord<f64
     }
 
     if in_coords.len() >= 3 {
-        // now remove redundant vertices which are, more or less, on the same straight line. the
-        // are covered by three point must be less than the triangle of three points of a hexagon
-        let out_ls = LineString::from(out);
-        let hexagon_corner_area =
-            Triangle::from([in_coords[0], in_coords[1], in_coords[2]]).unsigned_area();
-        out_ls.simplify_vw(&(hexagon_corner_area * 0.75)).0
+        // now remove redundant vertices which are, more or less, on the same straight line.
+        LineString::from(out).simplify_vw(&simplify_area).0
     } else {
         out
     }
 }
 
+/// Smoothen arbitrary geometries using the same algorithm as [`smoothen_h3_linked_polygon`].
+pub trait Smoothen {
+    /// Smoothen the geometry and remove redundant vertices.
+    ///
+    /// Vertices get removed when the corner they form with their neighbors has
+    /// an area less than `factor` times the area of a corner of a regular hexagon with the
+    /// mean segment length of the ring. So `0.0` keeps all vertices, while
+    /// larger factors remove more vertices. [`smoothen_h3_linked_polygon`] corresponds to a
+    /// `factor` of `0.75`.
+    #[must_use]
+    fn smoothen(&self, factor: f64) -> Self;
+}
+
+impl Smoothen for LineString<f64> {
+    fn smoothen(&self, factor: f64) -> Self {
+        let num_segments = self.0.len().saturating_sub(1);
+        if num_segments == 0 {
+            return self.clone();
+        }
+        let mean_segment_length = self
+            .lines()
+            .map(|line| line.dx().hypot(line.dy()))
+            .sum::<f64>()
+            / num_segments as f64;
+
+        // area of the triangle formed by a corner of a hexagon
+        let corner_area = 3.0_f64.sqrt() / 4.0 * mean_segment_length.powi(2);
+        Self::from(smoothen_coordinates(&self.0, corner_area * factor))
+    }
+}
+
+impl Smoothen for Polygon<f64> {
+    fn smoothen(&self, factor: f64) -> Self {
+        Self::new(
+            self.exterior().smoothen(factor),
+            self.interiors()
+                .iter()
+                .map(|ring| ring.smoothen(factor))
+                .collect(),
+        )
+    }
+}
+
+impl Smoothen for MultiPolygon<f64> {
+    fn smoothen(&self, factor: f64) -> Self {
+        self.iter().map(|poly| poly.smoothen(factor)).collect()
+    }
+}
+
 /// Smoothen a polygon to remove some of the artifacts of the h3indexes left after creating a h3 linkedpolygon.
 pub fn smoothen_h3_linked_polygon(in_poly: &Polygon<f64>) -> Polygon<f64> {
     Polygon::new(
@@ -79,7 +136,7 @@ mod tests {
     use geo::algorithm::coords_iter::CoordsIter;
     use geo_types::Coord;
 
-    use crate::algorithm::smoothen_h3_linked_polygon;
+    use crate::algorithm::{smoothen_h3_linked_polygon, Smoothen};
     use crate::{H3Cell, ToLinkedPolygons};
 
     #[test]
@@ -103,4 +160,43 @@ mod tests {
         assert_eq!(smoothed.interiors().len(), 1);
         assert!(smoothed.interiors()[0].coords_count() < 10);
     }
+
+    #[test]
+    fn smoothen_trait() {
+        use geo::algorithm::area::Area;
+        use geo::algorithm::contains::Contains;
+        use geo::algorithm::convex_hull::ConvexHull;
+        use geo_types::MultiPolygon;
+
+        let center = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), 8).unwrap();
+        let polygons = center
+            .grid_disk(5)
+            .unwrap()
+            .to_linked_polygons(false)
+            .unwrap();
+        assert_eq!(polygons.len(), 1);
+        let polygon = &polygons[0];
+
+        let smoothed = polygon.smoothen(0.75);
+        assert!(smoothed.exterior().coords_count() < polygon.exterior().coords_count());
+        assert!(smoothed.exterior().is_closed());
+        assert!(smoothed.exterior().coords_count() >= 4);
+
+        // the smoothed polygon still covers approx. the same area around the center
+        let relative_area_diff =
+            (smoothed.unsigned_area() - polygon.unsigned_area()).abs() / polygon.unsigned_area();
+        assert!(relative_area_diff < 0.05);
+        assert!(smoothed.contains(&center.to_point().unwrap()));
+        assert!(polygon.convex_hull().contains(&smoothed));
+
+        // a factor of 0.0 does not remove vertices
+        assert!(
+            polygon.smoothen(0.0).exterior().coords_count() >= polygon.exterior().coords_count()
+        );
+
+        let multi_polygon = MultiPolygon::new(vec![polygon.clone(), polygon.clone()]);
+        let smoothed_multi_polygon = multi_polygon.smoothen(0.75);
+        assert_eq!(smoothed_multi_polygon.0.len(), 2);
+        assert_eq!(smoothed_multi_polygon.0[0], smoothed);
+    }
 }
@@ -1,23 +1,44 @@
+use std::cmp::Ordering;
 use std::fmt::{self, Debug, Formatter};
 use std::ops::Deref;
 use std::os::raw::c_int;
 use std::str::FromStr;
 
-use geo_types::{Coord, Point, Polygon};
+use geo::{Closest, Contains, HaversineClosestPoint, HaversineDistance};
+use geo_types::{Coord, LineString, Point, Polygon, Triangle};
 #[cfg(feature = "use-serde")]
 use serde::{Deserialize, Serialize};
 
 use h3ron_h3_sys::H3Index;
 
 use crate::collections::indexvec::IndexVec;
-use crate::error::Error;
+use crate::error::{check_valid_h3_resolution, Error};
 use crate::index::{index_from_str, Index};
-use crate::iter::CellBoundaryBuilder;
-use crate::{max_grid_disk_size, FromH3Index, H3DirectedEdge, ToCoordinate, ToPolygon};
+use crate::iter::{CellBoundaryBuilder, GridDiskIter};
+use crate::xyz_tile::coordinate_to_xyz_tile;
+use crate::{
+    max_grid_disk_size, res0_cell_count, FromH3Index, H3DirectedEdge, H3Direction, H3Vertex,
+    ToCoordinate, ToPolygon, H3_MAX_RESOLUTION,
+};
+
+const H3_CELL_MODE: u64 = 1;
+const H3_MODE_OFFSET: u64 = 59;
+const H3_RES_OFFSET: u64 = 52;
+const H3_BC_OFFSET: u64 = 45;
+const H3_PER_DIGIT_OFFSET: u64 = 3;
+const H3_DIGITS_MASK: u64 = (1 << H3_BC_OFFSET) - 1;
+const H3_RESERVED_OFFSET: u64 = 56;
+const H3_RESERVED_MASK: u64 = 0b111;
+
+/// Rank of each H3 digit when ordering cells spatially. The non-center digits are
+/// ranked in the order they are located around the center, so consecutive children
+/// are neighbors of each other.
+const SPATIAL_DIGIT_RANK: [u8; 7] = [0, 1, 3, 2, 5, 6, 4];
 
 /// H3 Index representing a H3 Cell (hexagon)
 #[derive(PartialOrd, PartialEq, Clone, Hash, Eq, Ord, Copy)]
 #[cfg_attr(feature = "use-serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "use-serde", serde(transparent))]
 #[repr(transparent)]
 pub struct H3Cell(H3Index);
 
@@ -38,6 +59,24 @@ impl TryFrom<u64> for H3Cell {
     }
 }
 
+/// parse from the string representation. See [`H3Cell::from_str`].
+impl TryFrom<&str> for H3Cell {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str(s)
+    }
+}
+
+/// parse from the string representation. See [`H3Cell::from_str`].
+impl TryFrom<String> for H3Cell {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::from_str(&s)
+    }
+}
+
 impl FromH3Index for H3Cell {
     fn from_h3index(h3index: H3Index) -> Self {
         Self::new(h3index)
@@ -63,6 +102,20 @@ impl Index for H3Cell {
 }
 
 impl H3Cell {
+    /// The invalid cell with the h3index `0`.
+    ///
+    /// This is never a valid cell and is only intended to be used as a sentinel value
+    /// in places where a cell is required, but none is available.
+    pub const NULL: Self = Self(0);
+
+    /// The maximum number of decimal digits supported by [`H3Cell::to_quantized_lonlat`].
+    pub const MAX_LONLAT_PRECISION: u8 = 7;
+
+    /// `self` is the [`H3Cell::NULL`] sentinel.
+    pub const fn is_null(&self) -> bool {
+        self.0 == 0
+    }
+
     /// Build a new `Index` from a `Point`.
     ///
     /// # Returns
@@ -84,6 +137,56 @@ impl H3Cell {
         .map(|_| Self::new(cell_h3index))
     }
 
+    /// Same as [`H3Cell::from_coordinate`], but the coordinate is moved into the valid
+    /// WGS84 range first.
+    ///
+    /// The latitude is clamped to `-90..=90` and the longitude is wrapped around the
+    /// antimeridian into `-180..180`. This is intended for coordinates slightly outside of
+    /// the valid range due to floating point inaccuracies.
+    pub fn from_coordinate_clamped(c: Coord<f64>, h3_resolution: u8) -> Result<Self, Error> {
+        Self::from_coordinate(
+            Coord {
+                x: (c.x + 180.0).rem_euclid(360.0) - 180.0,
+                y: c.y.clamp(-90.0, 90.0),
+            },
+            h3_resolution,
+        )
+    }
+
+    /// Build a new `Index` from anything convertible to a coordinate, like
+    /// `Point`, `Coord` or `(x, y)` tuples.
+    ///
+    /// # Returns
+    /// If the built index is invalid, returns an Error.
+    pub fn from_geometry<G: Into<Coord<f64>>>(g: G, h3_resolution: u8) -> Result<Self, Error> {
+        Self::from_coordinate(g.into(), h3_resolution)
+    }
+
+    /// The centroid of `self` as a `Point`.
+    ///
+    /// Same as [`ToCoordinate::to_coordinate`], but returning a `Point`.
+    pub fn to_point(&self) -> Result<Point<f64>, Error> {
+        self.to_coordinate().map(Point::from)
+    }
+
+    /// The great-circle distance in meters from the centroid of `self` to the nearest
+    /// boundary of `polygon`.
+    ///
+    /// Returns `0.0` when the centroid is located within the polygon.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::Domain`] when `polygon` is degenerate and has no
+    /// well-defined nearest point.
+    pub fn distance_to_polygon_m(&self, polygon: &Polygon<f64>) -> Result<f64, Error> {
+        let centroid = self.to_point()?;
+        match polygon.haversine_closest_point(&centroid) {
+            Closest::Intersection(_) => Ok(0.0),
+            Closest::SinglePoint(closest) => Ok(centroid.haversine_distance(&closest)),
+            Closest::Indeterminate => Err(Error::Domain),
+        }
+    }
+
     /// Checks if `self` is a parent of `other`
     pub fn is_parent_of(&self, other: &Self) -> Result<bool, Error> {
         Ok(*self == other.get_parent(self.resolution())?)
@@ -114,19 +217,33 @@ impl H3Cell {
 
     /// Retrieves all children of `self` at resolution `child_resolution`
     pub fn get_children(&self, child_resolution: u8) -> Result<IndexVec<Self>, Error> {
-        let child_resolution = c_int::from(child_resolution);
+        let children_size = self.children_count(child_resolution)?;
+        let mut index_vec = IndexVec::with_length(children_size as usize);
 
-        let mut children_size: i64 = 0;
         Error::check_returncode(unsafe {
-            h3ron_h3_sys::cellToChildrenSize(self.h3index(), child_resolution, &mut children_size)
+            h3ron_h3_sys::cellToChildren(
+                self.h3index(),
+                c_int::from(child_resolution),
+                index_vec.as_mut_ptr(),
+            )
         })?;
+        Ok(index_vec)
+    }
 
-        let mut index_vec = IndexVec::with_length(children_size as usize);
-
+    /// Number of children of `self` at resolution `child_resolution` without
+    /// generating the children themselves.
+    ///
+    /// Pentagons have fewer children than hexagons.
+    pub fn children_count(&self, child_resolution: u8) -> Result<u64, Error> {
+        let mut children_size: i64 = 0;
         Error::check_returncode(unsafe {
-            h3ron_h3_sys::cellToChildren(self.h3index(), child_resolution, index_vec.as_mut_ptr())
+            h3ron_h3_sys::cellToChildrenSize(
+                self.h3index(),
+                c_int::from(child_resolution),
+                &mut children_size,
+            )
         })?;
-        Ok(index_vec)
+        Ok(children_size as u64)
     }
 
     /// Checks if the current index and `other` are neighbors.
@@ -138,6 +255,20 @@ impl H3Cell {
         .map(|_| res == 1)
     }
 
+    /// The subset of `candidates` which are neighbors of `self`.
+    ///
+    /// Fails when any of the candidates can not be checked - for example when it
+    /// has a different resolution than `self`.
+    pub fn neighbors_among<'a>(&self, candidates: &'a [Self]) -> Result<Vec<&'a Self>, Error> {
+        let mut neighbors = Vec::with_capacity(6.min(candidates.len()));
+        for candidate in candidates {
+            if self.are_neighbor_cells(*candidate)? {
+                neighbors.push(candidate);
+            }
+        }
+        Ok(neighbors)
+    }
+
     /// `grid_disk` produces all cells within k distance of the origin cell.
     ///
     /// k=0 is defined as the origin cell, k=1 is defined as k=0 + all
@@ -154,6 +285,30 @@ impl H3Cell {
         .map(|_| index_vec)
     }
 
+    /// The grid disk with radius `k` around `self`, restricted to the cells whose
+    /// centroid is located within `polygon`.
+    pub fn grid_disk_within_polygon(
+        &self,
+        k: u32,
+        polygon: &Polygon<f64>,
+    ) -> Result<IndexVec<Self>, Error> {
+        let mut within = IndexVec::new();
+        for cell in self.grid_disk(k)?.iter() {
+            if polygon.contains(&cell.to_coordinate()?) {
+                within.push(cell);
+            }
+        }
+        Ok(within)
+    }
+
+    /// Iterate over the cells of the grid disk with radius `k` around `self`, without
+    /// materializing the whole disk in memory.
+    ///
+    /// The cells are generated ring by ring starting at `self`. See [`GridDiskIter`].
+    pub const fn grid_disk_iter(&self, k: u32) -> GridDiskIter {
+        GridDiskIter::new(*self, k)
+    }
+
     /// hollow hexagon ring at `self`
     pub fn grid_ring_unsafe(&self, k: u32) -> Result<IndexVec<Self>, Error> {
         // calculation of max_size taken from
@@ -251,6 +406,23 @@ impl H3Cell {
         unsafe { h3ron_h3_sys::getBaseCellNumber(self.0) as u8 }
     }
 
+    /// The icosahedron faces intersected by `self`.
+    ///
+    /// Pentagons are located at the vertices of the icosahedron and intersect
+    /// five faces, most hexagons intersect only a single face.
+    pub fn icosahedron_faces(&self) -> Result<Vec<i32>, Error> {
+        let mut max_faces: c_int = 0;
+        Error::check_returncode(unsafe { h3ron_h3_sys::maxFaceCount(self.0, &mut max_faces) })?;
+
+        let mut faces: Vec<c_int> = vec![-1; max_faces as usize];
+        Error::check_returncode(unsafe {
+            h3ron_h3_sys::getIcosahedronFaces(self.0, faces.as_mut_ptr())
+        })?;
+        // unused slots are marked by -1
+        faces.retain(|face| *face >= 0);
+        Ok(faces)
+    }
+
     /// Gets the directed edge from `self` to `destination`
     ///
     /// # Returns
@@ -268,6 +440,18 @@ impl H3Cell {
         .map(|_| H3DirectedEdge::new(edge_h3index))
     }
 
+    /// Retrieves the direction from `self` toward the adjacent `neighbor`.
+    ///
+    /// This is the direction digit encoded in the directed edge between both cells.
+    ///
+    /// # Errors
+    ///
+    /// Fails when `neighbor` is not adjacent to `self`.
+    pub fn direction_to_neighbor(&self, neighbor: &Self) -> Result<H3Direction, Error> {
+        let edge = self.directed_edge_to(*neighbor)?;
+        H3Direction::try_from(((edge.h3index() >> H3_RESERVED_OFFSET) & H3_RESERVED_MASK) as u8)
+    }
+
     /// Retrieves all directed H3 edges around `self` where `self` is the origin
     ///
     /// For repeated creation of [`H3DirectedEdge`] around a [`H3Cell`] also
@@ -280,6 +464,61 @@ impl H3Cell {
         .map(|_| index_vec)
     }
 
+    /// Retrieves the boundary segment `self` shares with the adjacent cell `other`.
+    ///
+    /// The segment usually consists of two vertices. Segments crossing icosahedron edges
+    /// contain an additional distortion vertex.
+    ///
+    /// # Errors
+    ///
+    /// Fails when `other` is not adjacent to `self`.
+    pub fn shared_boundary(&self, other: &Self) -> Result<LineString<f64>, Error> {
+        self.directed_edge_to(*other)?.boundary_linestring()
+    }
+
+    /// Retrieves the vertex `vertex_num` of `self`.
+    ///
+    /// Vertices are numbered starting at 0. Hexagons have 6 vertices and pentagons 5.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::Domain`] when `vertex_num` does not exist for `self`.
+    pub fn vertex(&self, vertex_num: u8) -> Result<H3Vertex, Error> {
+        let mut vertex: H3Index = 0;
+        Error::check_returncode(unsafe {
+            h3ron_h3_sys::cellToVertex(self.h3index(), c_int::from(vertex_num), &mut vertex)
+        })
+        .map(|_| H3Vertex::new(vertex))
+    }
+
+    /// Retrieves all vertices of `self`, ordered by their vertex number.
+    pub fn vertexes(&self) -> Result<IndexVec<H3Vertex>, Error> {
+        let mut index_vec = IndexVec::with_length(6);
+        Error::check_returncode(unsafe {
+            h3ron_h3_sys::cellToVertexes(self.h3index(), index_vec.as_mut_ptr())
+        })
+        .map(|_| index_vec)
+    }
+
+    /// Retrieves the cells meeting at the vertex `vertex_num` of `self`, including `self`.
+    ///
+    /// Vertices are numbered starting at 0. Hexagons have 6 vertices and pentagons 5.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::Domain`] when `vertex_num` does not exist for `self`.
+    pub fn cells_sharing_vertex(&self, vertex_num: u8) -> Result<Vec<Self>, Error> {
+        let vertex = self.vertex(vertex_num)?;
+
+        let mut cells = Vec::with_capacity(3);
+        for cell in self.grid_disk(1)?.iter() {
+            if cell.vertexes()?.iter().any(|v| v == vertex) {
+                cells.push(cell);
+            }
+        }
+        Ok(cells)
+    }
+
     /// get the average cell area at `resolution` in square meters.
     ///
     /// ```
@@ -325,6 +564,97 @@ impl H3Cell {
             .map(|_| area)
     }
 
+    /// Create a cell at the given `h3_resolution` from a WKT point in the form of `POINT(lon lat)`.
+    ///
+    /// Requires the `wkt` feature.
+    #[cfg(feature = "wkt")]
+    pub fn from_wkt_point(wkt: &str, h3_resolution: u8) -> Result<Self, Error> {
+        use wkt::TryFromWkt;
+
+        let point: geo_types::Point<f64> = geo_types::Point::try_from_wkt_str(wkt)
+            .map_err(|e| Error::InvalidWkt(e.to_string()))?;
+        Self::from_coordinate(point.0, h3_resolution)
+    }
+
+    /// The id of the S2 cell at `level` containing the centroid of `self`.
+    ///
+    /// This is only an approximation, as the areas of H3 and S2 cells do not align. `level`
+    /// must be in the range `0..=30`.
+    ///
+    /// Requires the `s2` feature.
+    #[cfg(feature = "s2")]
+    pub fn to_s2_cell_id(&self, level: u8) -> Result<u64, Error> {
+        if u64::from(level) > s2::cellid::MAX_LEVEL {
+            return Err(Error::Domain);
+        }
+        let centroid = self.to_coordinate()?;
+        let latlng = s2::latlng::LatLng::from_degrees(centroid.y, centroid.x);
+        Ok(s2::cellid::CellID::from(latlng).parent(u64::from(level)).0)
+    }
+
+    /// Snap the centroid of `self` to the grid at `h3_resolution`.
+    ///
+    /// Returns the cell containing the centroid of `self` at `h3_resolution` and whether
+    /// that cell differs from `self`. At the resolution of `self` this is always `self`.
+    pub fn regrid(&self, h3_resolution: u8) -> Result<(Self, bool), Error> {
+        if h3_resolution == self.resolution() {
+            self.validate()?;
+            return Ok((*self, false));
+        }
+        let cell = Self::from_coordinate(self.to_coordinate()?, h3_resolution)?;
+        Ok((cell, cell != *self))
+    }
+
+    /// Tessellates `self` into a fan of triangles spanning from the centroid of
+    /// the cell to each edge of its boundary.
+    ///
+    /// Hexagons yield 6 triangles and pentagons 5. Cells with additional distortion
+    /// vertices in their boundary yield one triangle more for each of these vertices.
+    pub fn to_triangles(&self) -> Result<Vec<Triangle<f64>>, Error> {
+        let centroid = self.to_coordinate()?;
+        let vertices: Vec<Coord<f64>> = CellBoundaryBuilder::new()
+            .iter_cell_boundary_vertices(self, false)?
+            .collect();
+        Ok(vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .map(|(v1, v2)| Triangle::new(centroid, *v1, *v2))
+            .collect())
+    }
+
+    /// Tessellates `self` into a regular triangular lattice and returns the vertices of
+    /// the lattice.
+    ///
+    /// Each triangle of [`H3Cell::to_triangles`] is split into `subdivisions` segments along each
+    /// of its sides. Vertices shared between neighboring triangles are only returned once, so
+    /// a hexagon yields `1 + 6 * subdivisions * (subdivisions + 1) / 2` vertices.
+    pub fn tessellate(&self, subdivisions: u32) -> Result<Vec<Coord<f64>>, Error> {
+        if subdivisions == 0 {
+            return Err(Error::Domain);
+        }
+        let triangles = self.to_triangles()?;
+        let n = subdivisions as usize;
+        let mut vertices = Vec::with_capacity(1 + triangles.len() * n * (n + 1) / 2);
+
+        let centroid = self.to_coordinate()?;
+        vertices.push(centroid);
+        for triangle in triangles {
+            let d1 = triangle.v2() - centroid;
+            let d2 = triangle.v3() - centroid;
+
+            // i starts at 1 as the vertices along the edge from the centroid to `triangle.v3()` are
+            // part of the following triangle.
+            for i in 1..=n {
+                for j in 0..=(n - i) {
+                    let f1 = i as f64 / n as f64;
+                    let f2 = j as f64 / n as f64;
+                    vertices.push(centroid + d1 * f1 + d2 * f2);
+                }
+            }
+        }
+        Ok(vertices)
+    }
+
     /// returns the center child of `self` at the specified resolution.
     pub fn center_child(&self, resolution: u8) -> Result<Self, Error> {
         let mut cell_index: H3Index = 0;
@@ -337,6 +667,137 @@ impl H3Cell {
         })
         .map(|_| Self::new(cell_index))
     }
+
+    /// Strips the mode, resolution and base cell bits from `self` and returns
+    /// the remaining digits in the lowest `3 * resolution` bits.
+    ///
+    /// This allows storing cells in fewer bits when their base cell and resolution
+    /// are known from the context. Use [`H3Cell::from_local_bits`] for the reverse operation.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::Domain`] when `self` is not located within `base_cell`.
+    pub fn to_local_bits(&self, base_cell: u8) -> Result<u64, Error> {
+        if self.get_base_cell_number() != base_cell {
+            return Err(Error::Domain);
+        }
+        let unused_digits = u64::from(H3_MAX_RESOLUTION - self.resolution());
+        Ok((self.0 & H3_DIGITS_MASK) >> (unused_digits * H3_PER_DIGIT_OFFSET))
+    }
+
+    /// Reconstructs a cell from the representation created by [`H3Cell::to_local_bits`].
+    ///
+    /// # Errors
+    ///
+    /// Fails when `base_cell` or `resolution` are out of range, when `bits` does not fit
+    /// into `3 * resolution` bits, or when the reconstructed cell is invalid.
+    pub fn from_local_bits(bits: u64, base_cell: u8, resolution: u8) -> Result<Self, Error> {
+        check_valid_h3_resolution(resolution)?;
+        if base_cell >= res0_cell_count() {
+            return Err(Error::Domain);
+        }
+        let digits_len = u64::from(resolution) * H3_PER_DIGIT_OFFSET;
+        if bits >> digits_len != 0 {
+            return Err(Error::Domain);
+        }
+        let unused_digits_len = H3_BC_OFFSET - digits_len;
+        let h3index = (H3_CELL_MODE << H3_MODE_OFFSET)
+            | (u64::from(resolution) << H3_RES_OFFSET)
+            | (u64::from(base_cell) << H3_BC_OFFSET)
+            | (bits << unused_digits_len)
+            | ((1 << unused_digits_len) - 1);
+        Self::try_from(h3index)
+    }
+
+    /// Migrate a cell index created by version 3 of the H3 library.
+    ///
+    /// The bit layout of cell indexes did not change between version 3 and 4, but version 4
+    /// validates indexes more strictly, so the index is checked to be a valid v4 cell.
+    ///
+    /// Only cells are supported. Directed edges and vertices of version 3 are rejected
+    /// with [`Error::CellInvalid`].
+    pub fn migrate_from_v3(h3index_v3: u64) -> Result<Self, Error> {
+        if (h3index_v3 >> H3_MODE_OFFSET) & 0xf != H3_CELL_MODE {
+            return Err(Error::CellInvalid);
+        }
+        Self::try_from(h3index_v3)
+    }
+
+    /// The web-mercator XYZ tile at `zoom` containing the centroid of `self`.
+    ///
+    /// Returns the tile as a `(x, y, zoom)` tuple. See [`crate::xyz_tile::cells_in_xyz_tile`]
+    /// for the reverse operation.
+    pub fn to_xyz_tile(&self, zoom: u8) -> Result<(u32, u32, u8), Error> {
+        coordinate_to_xyz_tile(self.to_coordinate()?, zoom)
+    }
+
+    /// Encodes the centroid of `self` as a pair of fixed-point integers `(lng, lat)` with
+    /// `precision` decimal digits.
+    ///
+    /// Use [`H3Cell::from_quantized_lonlat`] for the reverse operation.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::Domain`] when `precision` exceeds [`H3Cell::MAX_LONLAT_PRECISION`] as the
+    /// coordinates would not fit into `i32` anymore.
+    pub fn to_quantized_lonlat(&self, precision: u8) -> Result<(i32, i32), Error> {
+        let factor = lonlat_precision_factor(precision)?;
+        let centroid = self.to_coordinate()?;
+        Ok((
+            (centroid.x * factor).round() as i32,
+            (centroid.y * factor).round() as i32,
+        ))
+    }
+
+    /// Reconstructs the cell at `h3_resolution` containing the coordinate encoded
+    /// by [`H3Cell::to_quantized_lonlat`].
+    ///
+    /// The reconstructed cell equals the original cell when `precision` is sufficient for
+    /// the resolution. A precision of 7 is enough for all resolutions.
+    pub fn from_quantized_lonlat(
+        lonlat: (i32, i32),
+        precision: u8,
+        h3_resolution: u8,
+    ) -> Result<Self, Error> {
+        let factor = lonlat_precision_factor(precision)?;
+        Self::from_coordinate(
+            Coord::from((f64::from(lonlat.0) / factor, f64::from(lonlat.1) / factor)),
+            h3_resolution,
+        )
+    }
+
+    /// Compares cells by their location instead of their raw index value.
+    ///
+    /// Cells are ordered by their base cell and then by the path of child positions
+    /// leading to them. The children of a cell are ordered in the sequence they are located
+    /// around the center child, so sorting cells using this method keeps neighboring
+    /// cells close to each other. Parents are ordered before their descendants.
+    pub fn cmp_spatial(&self, other: &Self) -> Ordering {
+        self.get_base_cell_number()
+            .cmp(&other.get_base_cell_number())
+            .then_with(|| {
+                let self_res = self.resolution();
+                let other_res = other.resolution();
+                for digit_res in 1..=self_res.min(other_res) {
+                    let offset = u64::from(H3_MAX_RESOLUTION - digit_res) * H3_PER_DIGIT_OFFSET;
+                    let self_digit = (self.0 >> offset) & 0b111;
+                    let other_digit = (other.0 >> offset) & 0b111;
+                    let ordering = SPATIAL_DIGIT_RANK[self_digit as usize]
+                        .cmp(&SPATIAL_DIGIT_RANK[other_digit as usize]);
+                    if ordering != Ordering::Equal {
+                        return ordering;
+                    }
+                }
+                self_res.cmp(&other_res)
+            })
+    }
+}
+
+fn lonlat_precision_factor(precision: u8) -> Result<f64, Error> {
+    if precision > H3Cell::MAX_LONLAT_PRECISION {
+        return Err(Error::Domain);
+    }
+    Ok(10_f64.powi(i32::from(precision)))
 }
 
 impl ToString for H3Cell {
@@ -352,7 +813,8 @@ impl FromStr for H3Cell {
     ///
     /// With the `parse` feature enabled this function is also able
     /// to parse strings containing integers and a custom coordinate-based format
-    /// in the form of `"x,y,resolution"`.
+    /// in the form of `"x,y,resolution"`. Integers may be given in decimal notation or
+    /// with a `0x` (hexadecimal) or `0o` (octal) prefix.
     ///
     /// Examples:
     ///
@@ -367,6 +829,9 @@ impl FromStr for H3Cell {
     ///     // parse from a string containing an integer
     ///     let index = H3Cell::from_str("617700169518678015").unwrap();
     ///
+    ///     // parse from a string containing a prefixed hexadecimal integer
+    ///     let index = H3Cell::from_str("0x89283080ddbffff").unwrap();
+    ///
     ///     // parse from coordinates and resolution
     ///     let index = H3Cell::from_str("23.3,12.3,6").unwrap();
     /// }
@@ -384,7 +849,7 @@ impl FromStr for H3Cell {
                 return Ok(cell);
             }
 
-            if let Ok(h3index) = u64::from_str(s) {
+            if let Some(h3index) = parse::parse_integer(s) {
                 return H3Cell::try_from(h3index);
             }
 
@@ -398,6 +863,36 @@ impl FromStr for H3Cell {
     }
 }
 
+#[cfg(feature = "parse")]
+impl H3Cell {
+    /// Parse a cell followed by a resolution offset in the form of `"<cell>+N"` and
+    /// return the children of the cell `N` resolutions finer.
+    ///
+    /// The cell may be given in any of the formats supported by [`H3Cell::from_str`].
+    ///
+    /// ```rust
+    /// use h3ron::H3Cell;
+    ///
+    /// let children = H3Cell::parse_cell_with_child_offset("89283080ddbffff+1").unwrap();
+    /// assert_eq!(children.count(), 7);
+    /// ```
+    pub fn parse_cell_with_child_offset(s: &str) -> Result<IndexVec<Self>, Error> {
+        let (cell_str, offset_str) = s.rsplit_once('+').ok_or(Error::Failed)?;
+        let offset_str = offset_str.trim();
+        if offset_str.is_empty() || !offset_str.chars().all(|c| c.is_ascii_digit()) {
+            return Err(Error::Failed);
+        }
+        let offset = u8::from_str(offset_str).map_err(|_| Error::ResDomain)?;
+
+        let cell = Self::from_str(cell_str)?;
+        let child_resolution = cell
+            .resolution()
+            .checked_add(offset)
+            .ok_or(Error::ResDomain)?;
+        cell.get_children(child_resolution)
+    }
+}
+
 #[cfg(feature = "parse")]
 mod parse {
     use geo_types::Coord;
@@ -422,6 +917,20 @@ mod parse {
         })(s)
     }
 
+    /// parse an integer in decimal notation, or in hexadecimal or octal notation
+    /// when prefixed with `0x` or `0o`.
+    pub(crate) fn parse_integer(s: &str) -> Option<u64> {
+        let s = s.trim();
+        let (digits, radix) = if let Some(hex) = s.strip_prefix("0x").or(s.strip_prefix("0X")) {
+            (hex, 16)
+        } else if let Some(oct) = s.strip_prefix("0o").or(s.strip_prefix("0O")) {
+            (oct, 8)
+        } else {
+            (s, 10)
+        };
+        u64::from_str_radix(digits, radix).ok()
+    }
+
     pub(crate) fn parse_coordinate_and_resolution(s: &str) -> IResult<&str, (Coord, u8)> {
         let (s, _) = take_while(is_whitespace)(s)?;
         let (s, x) = double(s)?;
@@ -467,8 +976,17 @@ impl Deref for H3Cell {
     }
 }
 
+/// `Borrow<H3Index>` is intentionally not implemented. It would make `Borrow<_>` bounds
+/// ambiguous for `H3Cell`, breaking type inference at existing call sites.
+impl AsRef<H3Index> for H3Cell {
+    fn as_ref(&self) -> &H3Index {
+        &self.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::cmp::Ordering;
     use std::collections::HashMap;
     use std::str::FromStr;
 
@@ -478,7 +996,7 @@ mod tests {
     use h3ron_h3_sys::H3Index;
 
     use crate::cell::H3Cell;
-    use crate::Index;
+    use crate::{H3Direction, Index};
 
     #[test]
     fn test_h3_to_string() {
@@ -501,6 +1019,18 @@ mod tests {
         assert_eq!(H3Cell::try_from(0x89283080ddbffff_u64).unwrap(), index);
     }
 
+    #[test]
+    fn test_try_from_str() -> Result<(), crate::Error> {
+        let c: H3Cell = "89283080ddbffff".try_into()?;
+        assert_eq!(c, H3Cell::new(0x89283080ddbffff_u64));
+
+        let c2: H3Cell = "89283080ddbffff".to_string().try_into()?;
+        assert_eq!(c, c2);
+
+        assert!(H3Cell::try_from("invalid").is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_is_valid() {
         assert!(H3Cell::try_from(0x89283080ddbffff_u64).unwrap().is_valid());
@@ -604,6 +1134,37 @@ mod tests {
         assert_eq!(idx, idx_2.h3index());
     }
 
+    #[cfg(feature = "use-serde")]
+    #[test]
+    fn serde_index_same_as_h3index() {
+        let idx = H3Cell::new(0x89283080ddbffff_u64);
+        assert_eq!(serialize(&idx).unwrap(), serialize(&idx.h3index()).unwrap());
+        assert_eq!(
+            serde_json::to_string(&idx).unwrap(),
+            serde_json::to_string(&idx.h3index()).unwrap()
+        );
+    }
+
+    #[cfg(feature = "use-serde")]
+    #[test]
+    fn serde_index_hex_json() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Location {
+            #[serde(with = "crate::serialize_hex")]
+            cell: H3Cell,
+        }
+
+        let location = Location {
+            cell: H3Cell::new(0x89283080ddbffff_u64),
+        };
+        let json = serde_json::to_string(&location).unwrap();
+        assert_eq!(json, r#"{"cell":"89283080ddbffff"}"#);
+
+        let location2: Location = serde_json::from_str(&json).unwrap();
+        assert_eq!(location2.cell, location.cell);
+        assert!(serde_json::from_str::<Location>(r#"{"cell":"zz"}"#).is_err());
+    }
+
     #[test]
     fn test_is_neighbor() {
         let idx: H3Cell = 0x89283080ddbffff_u64.try_into().unwrap();
@@ -628,6 +1189,453 @@ mod tests {
         assert_eq!(idx.grid_distance_to(neighbor).unwrap(), 3);
     }
 
+    #[test]
+    fn local_bits_roundtrip() {
+        let parent: H3Cell = 0x8518607bfffffff_u64.try_into().unwrap();
+        let base_cell = parent.get_base_cell_number();
+        let mut cells = parent.get_children(9).unwrap().iter().collect::<Vec<_>>();
+        cells.push(parent);
+        cells.push(parent.get_parent(0).unwrap());
+        cells.push(parent.center_child(15).unwrap());
+
+        for cell in cells {
+            assert_eq!(cell.get_base_cell_number(), base_cell);
+            let bits = cell.to_local_bits(base_cell).unwrap();
+            assert!(bits < (1 << (3 * cell.resolution())));
+            assert_eq!(
+                H3Cell::from_local_bits(bits, base_cell, cell.resolution()).unwrap(),
+                cell
+            );
+        }
+    }
+
+    #[test]
+    fn local_bits_wrong_base_cell() {
+        let cell: H3Cell = 0x89283080ddbffff_u64.try_into().unwrap();
+        let base_cell = cell.get_base_cell_number();
+        assert!(cell.to_local_bits(base_cell + 1).is_err());
+        assert!(H3Cell::from_local_bits(1 << 30, base_cell, 9).is_err());
+        assert!(H3Cell::from_local_bits(0, 122, 9).is_err());
+    }
+
+    #[test]
+    fn cmp_spatial_neighbor_adjacency() {
+        fn count_adjacent_neighbors(cells: &[H3Cell]) -> usize {
+            cells
+                .windows(2)
+                .filter(|w| w[0].are_neighbor_cells(w[1]).unwrap())
+                .count()
+        }
+
+        let mut cells = H3Cell::from_coordinate((12.0, 45.0).into(), 9)
+            .unwrap()
+            .grid_disk(20)
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+
+        cells.sort_unstable();
+        let raw_adjacent = count_adjacent_neighbors(&cells);
+
+        cells.sort_unstable_by(H3Cell::cmp_spatial);
+        let spatial_adjacent = count_adjacent_neighbors(&cells);
+
+        assert!(spatial_adjacent > raw_adjacent);
+    }
+
+    #[test]
+    fn from_geometry() {
+        use geo_types::{Coord, Point};
+
+        let expected = H3Cell::from_coordinate(Coord::from((12.3, 45.6)), 7).unwrap();
+        assert_eq!(
+            H3Cell::from_geometry(Point::new(12.3, 45.6), 7).unwrap(),
+            expected
+        );
+        assert_eq!(
+            H3Cell::from_geometry(Coord { x: 12.3, y: 45.6 }, 7).unwrap(),
+            expected
+        );
+        assert_eq!(H3Cell::from_geometry((12.3, 45.6), 7).unwrap(), expected);
+        assert!(H3Cell::from_geometry((12.3, 45.6), 16).is_err());
+    }
+
+    #[test]
+    fn direction_to_neighbor() {
+        let cell = H3Cell::from_coordinate((12.0, 45.0).into(), 7).unwrap();
+        let mut directions = cell
+            .grid_ring_unsafe(1)
+            .unwrap()
+            .iter()
+            .map(|neighbor| cell.direction_to_neighbor(&neighbor).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(directions.len(), 6);
+        assert!(!directions.contains(&H3Direction::CenterDigit));
+        directions.sort_unstable();
+        directions.dedup();
+        assert_eq!(directions.len(), 6);
+
+        assert!(cell.direction_to_neighbor(&cell).is_err());
+    }
+
+    #[test]
+    fn migrate_from_v3() {
+        // cell index taken from the documentation of h3 v3
+        let cell = H3Cell::migrate_from_v3(0x8928308280fffff).unwrap();
+        assert!(cell.validate().is_ok());
+        assert_eq!(cell.resolution(), 9);
+        assert_eq!(cell.h3index(), 0x8928308280fffff);
+
+        // v3 unidirectional edge
+        assert!(matches!(
+            H3Cell::migrate_from_v3(0x1189283080ddbfff),
+            Err(crate::Error::CellInvalid)
+        ));
+        assert!(H3Cell::migrate_from_v3(0).is_err());
+    }
+
+    #[test]
+    fn neighbors_among() {
+        let cell = H3Cell::from_coordinate((12.3, 45.6).into(), 8).unwrap();
+        let candidates: Vec<_> = cell.grid_disk(2).unwrap().iter().collect();
+        let neighbors = cell.neighbors_among(&candidates).unwrap();
+        assert_eq!(neighbors.len(), 6);
+        assert!(!neighbors.contains(&&cell));
+
+        let mut expected: Vec<_> = cell.grid_ring_unsafe(1).unwrap().iter().collect();
+        expected.sort_unstable();
+        let mut found: Vec<_> = neighbors.into_iter().copied().collect();
+        found.sort_unstable();
+        assert_eq!(found, expected);
+
+        assert!(cell.neighbors_among(&[]).unwrap().is_empty());
+        assert!(cell
+            .neighbors_among(&[cell.get_parent(7).unwrap()])
+            .is_err());
+    }
+
+    #[test]
+    fn icosahedron_faces() {
+        let hexagon = H3Cell::from_coordinate((12.0, 45.0).into(), 5).unwrap();
+        let faces = hexagon.icosahedron_faces().unwrap();
+        assert_eq!(faces.len(), 1);
+        assert!((0..20).contains(&faces[0]));
+
+        let pentagon = H3Cell::new(0x85080003fffffff);
+        assert!(pentagon.is_pentagon());
+        let faces = pentagon.icosahedron_faces().unwrap();
+        assert_eq!(faces.len(), 5);
+        assert!(faces.iter().all(|face| (0..20).contains(face)));
+    }
+
+    #[test]
+    fn children_count() {
+        let hexagon = H3Cell::from_coordinate((12.0, 45.0).into(), 5).unwrap();
+        assert!(!hexagon.is_pentagon());
+        assert_eq!(hexagon.children_count(5).unwrap(), 1);
+        assert_eq!(hexagon.children_count(8).unwrap(), 7_u64.pow(3));
+        assert_eq!(
+            hexagon.children_count(8).unwrap(),
+            hexagon.get_children(8).unwrap().count() as u64
+        );
+
+        let pentagon = H3Cell::new(0x85080003fffffff);
+        assert!(pentagon.is_pentagon());
+        assert!(pentagon.children_count(8).unwrap() < 7_u64.pow(3));
+        assert_eq!(
+            pentagon.children_count(8).unwrap(),
+            pentagon.get_children(8).unwrap().count() as u64
+        );
+
+        assert!(hexagon.children_count(4).is_err());
+    }
+
+    #[test]
+    fn to_triangles() {
+        use geo::GeodesicArea;
+
+        fn triangles_area_m2(cell: &H3Cell) -> f64 {
+            cell.to_triangles()
+                .unwrap()
+                .iter()
+                .map(|triangle| triangle.to_polygon().geodesic_area_unsigned())
+                .sum()
+        }
+
+        let hexagon = H3Cell::from_coordinate((12.0, 45.0).into(), 6).unwrap();
+        assert!(!hexagon.is_pentagon());
+        assert_eq!(hexagon.to_triangles().unwrap().len(), 6);
+        let area_m2 = hexagon.area_m2().unwrap();
+        assert!((triangles_area_m2(&hexagon) - area_m2).abs() / area_m2 < 0.01);
+
+        let pentagon = H3Cell::new(0x85080003fffffff).center_child(6).unwrap();
+        assert!(pentagon.is_pentagon());
+        assert_eq!(pentagon.to_triangles().unwrap().len(), 5);
+        let area_m2 = pentagon.area_m2().unwrap();
+        assert!((triangles_area_m2(&pentagon) - area_m2).abs() / area_m2 < 0.01);
+    }
+
+    #[cfg(feature = "wkt")]
+    #[test]
+    fn from_wkt_point() {
+        let cell = H3Cell::from_wkt_point("POINT(12.3 45.6)", 8).unwrap();
+        assert_eq!(
+            cell,
+            H3Cell::from_coordinate((12.3, 45.6).into(), 8).unwrap()
+        );
+
+        assert!(matches!(
+            H3Cell::from_wkt_point("LINESTRING(12.3 45.6, 13.0 46.0)", 8),
+            Err(crate::Error::InvalidWkt(_))
+        ));
+        assert!(matches!(
+            H3Cell::from_wkt_point("POINT(12.3", 8),
+            Err(crate::Error::InvalidWkt(_))
+        ));
+    }
+
+    #[cfg(feature = "s2")]
+    #[test]
+    fn to_s2_cell_id() {
+        use crate::ToCoordinate;
+
+        let cell = H3Cell::from_coordinate((12.3, 45.6).into(), 8).unwrap();
+        let centroid = cell.to_coordinate().unwrap();
+        let point =
+            s2::point::Point::from(s2::latlng::LatLng::from_degrees(centroid.y, centroid.x));
+
+        for level in [0, 5, 13, 30] {
+            let cell_id = s2::cellid::CellID(cell.to_s2_cell_id(level).unwrap());
+            assert!(cell_id.is_valid());
+            assert_eq!(cell_id.level(), u64::from(level));
+            assert!(s2::cell::Cell::from(cell_id).contains_point(&point));
+        }
+        assert!(cell.to_s2_cell_id(31).is_err());
+    }
+
+    #[test]
+    fn regrid() {
+        let parent = H3Cell::from_coordinate((12.3, 45.6).into(), 6).unwrap();
+        let cell = parent.center_child(9).unwrap();
+
+        assert_eq!(cell.regrid(9).unwrap(), (cell, false));
+        assert_eq!(cell.regrid(6).unwrap(), (parent, true));
+        assert_eq!(parent.regrid(9).unwrap(), (cell, true));
+        assert!(H3Cell::NULL.regrid(0).is_err());
+    }
+
+    #[test]
+    fn null_cell() {
+        assert!(H3Cell::NULL.is_null());
+        assert!(H3Cell::NULL.validate().is_err());
+        assert_eq!(H3Cell::NULL, H3Cell::new(0));
+        assert!(!H3Cell::new(0x89283080ddbffff_u64).is_null());
+    }
+
+    #[test]
+    fn tessellate() {
+        use crate::ToPolygon;
+        use geo::{Contains, EuclideanDistance};
+        use geo_types::Point;
+
+        let cell = H3Cell::from_coordinate((12.0, 45.0).into(), 6).unwrap();
+        let polygon = cell.to_polygon().unwrap();
+
+        let mut previous_count = 0;
+        for subdivisions in 1..5 {
+            let vertices = cell.tessellate(subdivisions).unwrap();
+            let n = subdivisions as usize;
+            assert_eq!(vertices.len(), 1 + 6 * n * (n + 1) / 2);
+            assert!(vertices.len() > previous_count);
+            previous_count = vertices.len();
+
+            for vertex in vertices {
+                let point = Point::from(vertex);
+                assert!(
+                    polygon.contains(&point)
+                        || polygon.exterior().euclidean_distance(&point) < 1e-9
+                );
+            }
+        }
+
+        assert!(cell.tessellate(0).is_err());
+    }
+
+    #[test]
+    fn quantized_lonlat_roundtrip() {
+        for res in [0, 5, 10, 15] {
+            let cell = H3Cell::from_coordinate((-122.4089, 37.7813).into(), res).unwrap();
+            let lonlat = cell.to_quantized_lonlat(7).unwrap();
+            assert_eq!(H3Cell::from_quantized_lonlat(lonlat, 7, res).unwrap(), cell);
+        }
+
+        let cell = H3Cell::from_coordinate((179.99, -89.99).into(), 15).unwrap();
+        let lonlat = cell.to_quantized_lonlat(7).unwrap();
+        assert_eq!(H3Cell::from_quantized_lonlat(lonlat, 7, 15).unwrap(), cell);
+
+        assert!(cell.to_quantized_lonlat(8).is_err());
+        assert!(H3Cell::from_quantized_lonlat((0, 0), 8, 5).is_err());
+    }
+
+    #[test]
+    fn cells_sharing_vertex() {
+        let cell = H3Cell::from_coordinate((12.3, 45.6).into(), 8).unwrap();
+        let mut all_neighbors = Vec::new();
+        for vertex_num in 0..6 {
+            let cells = cell.cells_sharing_vertex(vertex_num).unwrap();
+            assert_eq!(cells.len(), 3);
+            assert!(cells.contains(&cell));
+            for other in cells.iter().filter(|other| **other != cell) {
+                assert!(cell.are_neighbor_cells(*other).unwrap());
+            }
+            all_neighbors.extend(cells.into_iter().filter(|other| *other != cell));
+        }
+        all_neighbors.sort_unstable();
+        all_neighbors.dedup();
+        assert_eq!(all_neighbors.len(), 6);
+
+        assert!(cell.cells_sharing_vertex(6).is_err());
+    }
+
+    #[test]
+    fn from_coordinate_clamped() {
+        let cell = H3Cell::from_coordinate_clamped((12.0, 90.0001).into(), 7).unwrap();
+        assert_eq!(
+            cell,
+            H3Cell::from_coordinate((12.0, 90.0).into(), 7).unwrap()
+        );
+
+        let cell = H3Cell::from_coordinate_clamped((181.5, -90.5).into(), 7).unwrap();
+        assert_eq!(
+            cell,
+            H3Cell::from_coordinate((-178.5, -90.0).into(), 7).unwrap()
+        );
+
+        let cell = H3Cell::from_coordinate_clamped((-200.0, 45.0).into(), 7).unwrap();
+        assert_eq!(
+            cell,
+            H3Cell::from_coordinate((160.0, 45.0).into(), 7).unwrap()
+        );
+
+        assert!(H3Cell::from_coordinate_clamped((f64::NAN, 45.0).into(), 7).is_err());
+    }
+
+    #[test]
+    fn shared_boundary() {
+        use crate::ToPolygon;
+        use geo::EuclideanDistance;
+        use geo_types::Point;
+
+        let cell = H3Cell::from_coordinate((12.3, 45.6).into(), 8).unwrap();
+        let cell_exterior = cell.to_polygon().unwrap().exterior().clone();
+        for neighbor in cell.grid_ring_unsafe(1).unwrap().iter() {
+            let boundary = cell.shared_boundary(&neighbor).unwrap();
+            assert_eq!(boundary.0.len(), 2);
+
+            let neighbor_exterior = neighbor.to_polygon().unwrap().exterior().clone();
+            for coord in boundary.coords() {
+                let point = Point::from(*coord);
+                assert!(cell_exterior.euclidean_distance(&point) < 1e-9);
+                assert!(neighbor_exterior.euclidean_distance(&point) < 1e-9);
+            }
+        }
+
+        let not_adjacent = cell.grid_ring_unsafe(2).unwrap().first().unwrap();
+        assert!(cell.shared_boundary(&not_adjacent).is_err());
+    }
+
+    #[test]
+    fn to_point() {
+        use crate::ToCoordinate;
+        use geo_types::Point;
+
+        let cell = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
+        assert_eq!(
+            cell.to_point().unwrap(),
+            Point::from(cell.to_coordinate().unwrap())
+        );
+        assert_eq!(
+            H3Cell::from_point(cell.to_point().unwrap(), 9).unwrap(),
+            cell
+        );
+    }
+
+    #[test]
+    fn distance_to_polygon_m() {
+        use geo_types::{LineString, Polygon};
+
+        let polygon = Polygon::new(
+            LineString::from(vec![
+                (11.9, 44.9),
+                (12.1, 44.9),
+                (12.1, 45.1),
+                (11.9, 45.1),
+                (11.9, 44.9),
+            ]),
+            vec![],
+        );
+        let inside = H3Cell::from_coordinate((12.0, 45.0).into(), 9).unwrap();
+        assert_eq!(inside.distance_to_polygon_m(&polygon).unwrap(), 0.0);
+
+        // 0.9 degrees of longitude at 45 degrees latitude are approx. 70.8km
+        let outside = H3Cell::from_coordinate((13.0, 45.0).into(), 9).unwrap();
+        let distance = outside.distance_to_polygon_m(&polygon).unwrap();
+        assert!(distance > 70_000.0);
+        assert!(distance < 71_500.0);
+
+        let far_away = H3Cell::from_coordinate((-60.0, -30.0).into(), 9).unwrap();
+        assert!(far_away.distance_to_polygon_m(&polygon).unwrap() > 5_000_000.0);
+    }
+
+    #[test]
+    fn grid_disk_within_polygon() {
+        use crate::ToCoordinate;
+        use geo_types::{LineString, Polygon};
+
+        let center = H3Cell::from_coordinate((12.0, 45.0).into(), 9).unwrap();
+        let center_coord = center.to_coordinate().unwrap();
+
+        // clip the disk to the half east of the centroid of `center`
+        let polygon = Polygon::new(
+            LineString::from(vec![
+                (center_coord.x - 1e-6, 44.0),
+                (13.0, 44.0),
+                (13.0, 46.0),
+                (center_coord.x - 1e-6, 46.0),
+                (center_coord.x - 1e-6, 44.0),
+            ]),
+            vec![],
+        );
+        let disk = center.grid_disk(3).unwrap();
+        let within = center.grid_disk_within_polygon(3, &polygon).unwrap();
+        assert!(!within.is_empty());
+        assert!(within.count() < disk.count());
+        assert!(within.iter().any(|cell| cell == center));
+        for cell in disk.iter() {
+            let is_east = cell.to_coordinate().unwrap().x >= center_coord.x - 1e-6;
+            assert_eq!(within.iter().any(|c| c == cell), is_east);
+        }
+    }
+
+    #[test]
+    fn as_ref_h3index() {
+        fn raw_index<T: AsRef<u64>>(value: T) -> u64 {
+            *value.as_ref()
+        }
+
+        let cell = H3Cell::from_coordinate((12.3, 45.6).into(), 7).unwrap();
+        assert_eq!(raw_index(cell), cell.h3index());
+    }
+
+    #[test]
+    fn cmp_spatial_parent_first() {
+        let cell: H3Cell = 0x89283080ddbffff_u64.try_into().unwrap();
+        let parent = cell.get_parent(6).unwrap();
+        assert_eq!(parent.cmp_spatial(&cell), Ordering::Less);
+        assert_eq!(cell.cmp_spatial(&parent), Ordering::Greater);
+        assert_eq!(cell.cmp_spatial(&cell), Ordering::Equal);
+    }
+
     mod edges {
         use super::*;
 
@@ -701,6 +1709,42 @@ mod tests {
             assert_eq!(cell, cell2);
         }
 
+        #[test]
+        fn parse_cell_from_prefixed_integer() {
+            let cell = H3Cell::from_str("89283080ddbffff").unwrap();
+            assert_eq!(H3Cell::from_str("0x89283080ddbffff").unwrap(), cell);
+            assert_eq!(H3Cell::from_str("0X89283080DDBFFFF").unwrap(), cell);
+
+            let s = format!("0o{:o}", cell.h3index());
+            assert_eq!(H3Cell::from_str(&s).unwrap(), cell);
+
+            assert!(H3Cell::from_str("0xinvalid").is_err());
+        }
+
+        #[test]
+        fn parse_cell_with_child_offset() {
+            let cell = H3Cell::from_str("89283080ddbffff").unwrap();
+            let children = H3Cell::parse_cell_with_child_offset("89283080ddbffff+1").unwrap();
+            assert_eq!(children.count(), 7);
+            assert!(children
+                .iter()
+                .all(|child| child.resolution() == 10 && child.is_child_of(&cell).unwrap()));
+
+            let children = H3Cell::parse_cell_with_child_offset("89283080ddbffff+0").unwrap();
+            assert_eq!(children.iter().collect::<Vec<_>>(), vec![cell]);
+            assert_eq!(
+                H3Cell::parse_cell_with_child_offset("89283080ddbffff+2")
+                    .unwrap()
+                    .count(),
+                49
+            );
+
+            assert!(H3Cell::parse_cell_with_child_offset("89283080ddbffff").is_err());
+            assert!(H3Cell::parse_cell_with_child_offset("89283080ddbffff+").is_err());
+            assert!(H3Cell::parse_cell_with_child_offset("89283080ddbffff+x").is_err());
+            assert!(H3Cell::parse_cell_with_child_offset("89283080ddbffff+7").is_err());
+        }
+
         #[test]
         fn parse_cell_from_coordinate_and_resolution() {
             let cell: H3Cell = 0x89283080ddbffff_u64.try_into().unwrap();
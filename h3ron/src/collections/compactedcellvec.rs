@@ -194,6 +194,16 @@ impl CompactedCellVec {
         self.purge_children()
     }
 
+    /// sort the cells of each resolution.
+    ///
+    /// The contents are not changed, this just makes the order of the cells returned by the
+    /// iterators of this struct independent of the order the cells have been added in.
+    pub fn sort(&mut self) {
+        self.cells_by_resolution
+            .iter_mut()
+            .for_each(|cells| cells.sort_unstable());
+    }
+
     /// the finest resolution contained
     pub fn finest_resolution_contained(&self) -> Option<u8> {
         for resolution in H3_RESOLUTION_RANGE_USIZE.rev() {
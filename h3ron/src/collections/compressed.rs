@@ -1,4 +1,6 @@
 use std::borrow::Borrow;
+use std::hash::{Hash, Hasher};
+use std::io::{ErrorKind, Read, Write};
 use std::marker::PhantomData;
 use std::mem::size_of;
 
@@ -21,7 +23,11 @@ use crate::{Error, Index, IndexVec};
 /// h3index. For spatially close h3index this results in a quite good compression ratio as many
 /// bytes are common over many h3indexes. As an example: a k-ring with `k=50` and 7651 cells
 /// compresses from 61kb to around 7.6kb.
-#[derive(Clone, PartialEq, Eq, Debug)]
+///
+/// The encoding is deterministic: blocks created from the same sequence of indexes always
+/// contain the same compressed bytes. So comparing and hashing blocks works on the compressed
+/// data without decompressing it. Note that the order of the indexes is relevant for this.
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "use-serde", derive(Serialize, Deserialize))]
 #[cfg_attr(
     feature = "use-serde",
@@ -117,6 +123,124 @@ where
     }
 }
 
+impl<T> IndexBlock<T> {
+    /// Write `blocks` sequentially to `w`.
+    ///
+    /// Each block is prefixed by its number of indexes and the length of its compressed
+    /// data, so the blocks can be read back one at a time using [`IndexBlock::read_many`].
+    pub fn write_many<W: Write>(blocks: &[Self], w: &mut W) -> Result<(), Error> {
+        for block in blocks {
+            w.write_all(&(block.num_indexes as u64).to_le_bytes())?;
+            w.write_all(&(block.block_data.len() as u64).to_le_bytes())?;
+            w.write_all(&block.block_data)?;
+        }
+        Ok(())
+    }
+
+    /// Read the blocks written by [`IndexBlock::write_many`] from `r`.
+    ///
+    /// The blocks are decoded lazily one at a time by the returned iterator.
+    pub const fn read_many<R: Read>(r: R) -> IndexBlockReader<R, T> {
+        IndexBlockReader {
+            reader: r,
+            phantom_data: PhantomData,
+        }
+    }
+}
+
+/// Iterator over the blocks written by [`IndexBlock::write_many`]. Created
+/// by [`IndexBlock::read_many`].
+pub struct IndexBlockReader<R, T> {
+    reader: R,
+    phantom_data: PhantomData<T>,
+}
+
+impl<R, T> IndexBlockReader<R, T>
+where
+    R: Read,
+{
+    fn read_u64(&mut self) -> Result<u64, Error> {
+        let mut buf = [0u8; size_of::<u64>()];
+        self.reader.read_exact(&mut buf)?;
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    /// Read the next block. Returns `None` when the end of the input has been reached
+    /// at a block boundary.
+    fn read_block(&mut self) -> Result<Option<IndexBlock<T>>, Error> {
+        let mut buf = [0u8; size_of::<u64>()];
+        let mut filled = 0;
+        while filled < buf.len() {
+            match self.reader.read(&mut buf[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => return Err(std::io::Error::from(ErrorKind::UnexpectedEof).into()),
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        let num_indexes = u64::from_le_bytes(buf);
+        let num_bytes = self.read_u64()?;
+
+        // the length prefix is not trusted for allocating the buffer up front, so a corrupt
+        // prefix results in an error instead of a failing allocation.
+        let mut block_data = Vec::new();
+        (&mut self.reader)
+            .take(num_bytes)
+            .read_to_end(&mut block_data)?;
+        if block_data.len() as u64 != num_bytes {
+            return Err(std::io::Error::from(ErrorKind::UnexpectedEof).into());
+        }
+
+        let mut num_decoded_bytes = 0_u64;
+        rle_decode_step_bytes(&block_data, |_, repetitions| {
+            num_decoded_bytes += u64::from(repetitions);
+            true
+        })?;
+        if Some(num_decoded_bytes) != num_indexes.checked_mul(size_of::<u64>() as u64) {
+            return Err(Error::DecompressionError(format!(
+                "Expected IndexBlock of {} indexes, found {} uncompressed bytes",
+                num_indexes, num_decoded_bytes
+            )));
+        }
+        let num_indexes = num_indexes as usize;
+
+        Ok(Some(IndexBlock {
+            num_indexes,
+            block_data: block_data.into_boxed_slice(),
+            phantom_data: PhantomData,
+        }))
+    }
+}
+
+impl<R, T> Iterator for IndexBlockReader<R, T>
+where
+    R: Read,
+{
+    type Item = Result<IndexBlock<T>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_block().transpose()
+    }
+}
+
+impl<T> PartialEq for IndexBlock<T> {
+    fn eq(&self, other: &Self) -> bool {
+        // comparing the lengths first allows skipping the comparison of the block data
+        // for most non-equal blocks.
+        self.num_indexes == other.num_indexes && self.block_data == other.block_data
+    }
+}
+
+impl<T> Eq for IndexBlock<T> {}
+
+impl<T> Hash for IndexBlock<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.num_indexes.hash(state);
+        self.block_data.hash(state);
+    }
+}
+
 impl<T> From<&[T]> for IndexBlock<T>
 where
     T: Index,
@@ -387,7 +511,7 @@ fn rle_encode(bytes: &[u8], out: &mut Vec<u8>) {
 #[cfg(test)]
 mod tests {
     use crate::collections::compressed::Decompressor;
-    use crate::H3Cell;
+    use crate::{Error, H3Cell};
 
     use super::IndexBlock;
 
@@ -457,6 +581,59 @@ mod tests {
         assert_eq!(ring.len(), ib.iter_uncompressed().unwrap().count());
     }
 
+    #[test]
+    fn test_write_read_many() {
+        let blocks = vec![
+            IndexBlock::from(make_grid_disk(1).as_slice()),
+            IndexBlock::from(Vec::<H3Cell>::new()),
+            IndexBlock::from(make_grid_disk(4).as_slice()),
+        ];
+        let mut buf = Vec::new();
+        IndexBlock::write_many(&blocks, &mut buf).unwrap();
+
+        let read_blocks = IndexBlock::<H3Cell>::read_many(buf.as_slice())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(read_blocks, blocks);
+        assert_eq!(
+            read_blocks[2]
+                .iter_uncompressed()
+                .unwrap()
+                .collect::<Vec<_>>(),
+            make_grid_disk(4)
+        );
+
+        // truncated input
+        let mut reader = IndexBlock::<H3Cell>::read_many(&buf[..buf.len() - 1]);
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_read_many_corrupt_prefix() {
+        let mut buf = Vec::new();
+        IndexBlock::write_many(&[IndexBlock::from(make_grid_disk(2).as_slice())], &mut buf)
+            .unwrap();
+
+        // byte length exceeding the available input
+        let mut corrupt = buf.clone();
+        corrupt[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        let mut reader = IndexBlock::<H3Cell>::read_many(corrupt.as_slice());
+        assert!(matches!(reader.next(), Some(Err(Error::IOError(_)))));
+
+        // number of indexes not matching the compressed data
+        for num_indexes in [0, 1, u64::MAX] {
+            let mut corrupt = buf.clone();
+            corrupt[0..8].copy_from_slice(&num_indexes.to_le_bytes());
+            let mut reader = IndexBlock::<H3Cell>::read_many(corrupt.as_slice());
+            assert!(matches!(
+                reader.next(),
+                Some(Err(Error::DecompressionError(_)))
+            ));
+        }
+    }
+
     #[cfg(feature = "use-serde")]
     #[test]
     fn serde_roundtrip() {
@@ -468,6 +645,32 @@ mod tests {
         assert_eq!(ib, ib_de);
     }
 
+    #[test]
+    fn test_indexblock_eq_and_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash_of(ib: &IndexBlock<H3Cell>) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            ib.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let ib1 = IndexBlock::from(make_grid_disk(3).as_slice());
+        let ib2: IndexBlock<H3Cell> = IndexBlock::from_iter(make_grid_disk(3).iter());
+        assert_eq!(ib1, ib2);
+        assert_eq!(hash_of(&ib1), hash_of(&ib2));
+
+        let ib3 = IndexBlock::from(make_grid_disk(2).as_slice());
+        assert_ne!(ib1, ib3);
+
+        let mut set = crate::collections::HashSet::default();
+        set.insert(ib1);
+        set.insert(ib2);
+        set.insert(ib3);
+        assert_eq!(set.len(), 2);
+    }
+
     #[test]
     fn test_indexblock_contains() {
         let cell = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
@@ -1,4 +1,7 @@
-use crate::{Error, FromH3Index, Index};
+use crate::collections::CompactedCellVec;
+#[cfg(feature = "roaring")]
+use crate::collections::H3Treemap;
+use crate::{Error, FromH3Index, H3Cell, Index};
 use h3ron_h3_sys::H3Index;
 use std::marker::PhantomData;
 
@@ -137,6 +140,22 @@ impl<T: FromH3Index + Index> IndexVec<T> {
     pub fn push(&mut self, item: T) {
         self.inner_vec.push(item.h3index());
     }
+
+    /// convert into a [`H3Treemap`]. Duplicates are removed.
+    #[cfg(feature = "roaring")]
+    pub fn into_treemap(self) -> H3Treemap<T> {
+        H3Treemap::from_iter_with_sort(self.iter_unchecked())
+    }
+}
+
+impl IndexVec<H3Cell> {
+    /// convert into a compacted [`CompactedCellVec`]. Duplicates are removed.
+    pub fn into_compacted(self) -> Result<CompactedCellVec, Error> {
+        let mut compacted_vec = CompactedCellVec::new();
+        compacted_vec.add_cells(self.iter_unchecked(), false)?;
+        compacted_vec.compact()?;
+        Ok(compacted_vec)
+    }
 }
 
 impl<'a, T: FromH3Index + Index> IntoIterator for &'a IndexVec<T> {
@@ -241,3 +260,54 @@ impl<T: FromH3Index + Index> TryFrom<Vec<H3Index>> for IndexVec<T> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use geo_types::Coord;
+
+    use crate::H3Cell;
+
+    #[cfg(feature = "roaring")]
+    #[test]
+    fn into_treemap() {
+        let disk = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), 6)
+            .unwrap()
+            .grid_disk(3)
+            .unwrap();
+        let cells = disk.iter().collect::<Vec<_>>();
+
+        let treemap = disk.into_treemap();
+        assert_eq!(treemap.len(), cells.len());
+        assert!(cells.iter().all(|cell| treemap.contains(cell)));
+    }
+
+    #[test]
+    fn into_compacted() {
+        let parent = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), 6).unwrap();
+        let mut children = parent.get_children(8).unwrap();
+        let mut other = parent
+            .grid_disk(2)
+            .unwrap()
+            .iter()
+            .last()
+            .unwrap()
+            .get_children(8)
+            .unwrap();
+        let num_children = children.count() + other.count();
+        children.append(&mut other);
+
+        let compacted = children.into_compacted().unwrap();
+        assert_eq!(compacted.len(), 2);
+        assert_eq!(
+            compacted
+                .iter_uncompacted_cells(8)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+                .len(),
+            num_children
+        );
+        assert!(compacted
+            .get_compacted_cells_at_resolution(6)
+            .contains(&parent));
+    }
+}
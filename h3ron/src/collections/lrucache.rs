@@ -0,0 +1,124 @@
+use std::num::NonZeroUsize;
+
+use lru::LruCache;
+
+use crate::collections::RandomState;
+use crate::H3Cell;
+
+/// Fixed-capacity least-recently-used cache keyed by [`H3Cell`].
+///
+/// Intended for caching the results of expensive per-cell computations like
+/// the generation of polygons. Once the capacity is exceeded, the least recently used
+/// entry gets evicted.
+///
+/// Requires the `lru` feature.
+pub struct H3CellLruCache<V> {
+    cache: LruCache<H3Cell, V, RandomState>,
+}
+
+impl<V> H3CellLruCache<V> {
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            cache: LruCache::with_hasher(capacity, RandomState::default()),
+        }
+    }
+
+    /// Get the value of `cell`, computing it using `f` if it is not cached.
+    ///
+    /// Marks `cell` as the most recently used entry.
+    pub fn get_or_insert_with<F>(&mut self, cell: H3Cell, f: F) -> &V
+    where
+        F: FnOnce() -> V,
+    {
+        self.cache.get_or_insert(cell, f)
+    }
+
+    /// Same as [`H3CellLruCache::get_or_insert_with`], but for fallible computations.
+    ///
+    /// Errors are returned and not cached.
+    pub fn try_get_or_insert_with<F, E>(&mut self, cell: H3Cell, f: F) -> Result<&V, E>
+    where
+        F: FnOnce() -> Result<V, E>,
+    {
+        self.cache.try_get_or_insert(cell, f)
+    }
+
+    /// Get the cached value of `cell` and mark it as the most recently used entry.
+    pub fn get(&mut self, cell: &H3Cell) -> Option<&V> {
+        self.cache.get(cell)
+    }
+
+    /// Check if `cell` is cached without changing its position in the cache.
+    pub fn contains(&self, cell: &H3Cell) -> bool {
+        self.cache.contains(cell)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn capacity(&self) -> NonZeroUsize {
+        self.cache.cap()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use crate::H3Cell;
+
+    use super::H3CellLruCache;
+
+    #[test]
+    fn evict_least_recently_used() {
+        let cells = H3Cell::from_coordinate((12.3, 45.6).into(), 8)
+            .unwrap()
+            .grid_disk(1)
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+
+        let mut cache = H3CellLruCache::new(NonZeroUsize::new(3).unwrap());
+        for (i, cell) in cells.iter().take(3).enumerate() {
+            assert_eq!(*cache.get_or_insert_with(*cell, || i), i);
+        }
+        assert_eq!(cache.len(), 3);
+
+        // use the first cell again, so the second one is the least recently used
+        assert_eq!(*cache.get_or_insert_with(cells[0], || 100), 0);
+
+        cache.get_or_insert_with(cells[3], || 3);
+        assert_eq!(cache.len(), 3);
+        assert!(cache.contains(&cells[0]));
+        assert!(!cache.contains(&cells[1]));
+        assert!(cache.contains(&cells[2]));
+        assert!(cache.contains(&cells[3]));
+
+        // evicted values get recomputed
+        assert_eq!(*cache.get_or_insert_with(cells[1], || 101), 101);
+        assert!(!cache.contains(&cells[2]));
+    }
+
+    #[test]
+    fn errors_are_not_cached() {
+        let cell = H3Cell::from_coordinate((12.3, 45.6).into(), 8).unwrap();
+        let mut cache = H3CellLruCache::new(NonZeroUsize::new(2).unwrap());
+        assert!(cache
+            .try_get_or_insert_with(cell, || Err::<u32, _>("failed"))
+            .is_err());
+        assert!(cache.is_empty());
+        assert_eq!(
+            cache.try_get_or_insert_with(cell, || Ok::<_, ()>(4)),
+            Ok(&4)
+        );
+    }
+}
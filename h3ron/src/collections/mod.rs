@@ -16,7 +16,9 @@ pub use ahash::RandomState;
 pub use hashbrown;
 
 pub use compactedcellvec::CompactedCellVec;
-pub use compressed::{Decompressor, IndexBlock};
+pub use compressed::{Decompressor, IndexBlock, IndexBlockReader};
+#[cfg(feature = "lru")]
+pub use lrucache::H3CellLruCache;
 #[cfg(feature = "roaring")]
 pub use treemap::H3Treemap;
 
@@ -25,6 +27,8 @@ use crate::{H3Cell, H3DirectedEdge, Index};
 pub mod compactedcellvec;
 pub mod compressed;
 pub mod indexvec;
+#[cfg(feature = "lru")]
+pub mod lrucache;
 #[cfg(feature = "roaring")]
 pub mod treemap;
 
@@ -40,6 +44,18 @@ pub type H3EdgeMap<V> = HashMap<H3DirectedEdge, V>;
 pub type H3CellMap<V> = HashMap<H3Cell, V>;
 pub type H3CellSet = HashSet<H3Cell>;
 
+/// Build a [`H3CellMap`] from an iterator of `(H3Cell, V)` tuples sorted by the cell.
+///
+/// This is a plain `collect()`, which already reserves the capacity of the map from the size
+/// hint of the iterator. Same as with `collect()` the last value of a duplicated cell is kept,
+/// and unsorted input results in the same map.
+pub fn h3cellmap_from_sorted<V, I>(iter: I) -> H3CellMap<V>
+where
+    I: IntoIterator<Item = (H3Cell, V)>,
+{
+    iter.into_iter().collect()
+}
+
 impl<I: Index + Eq + Hash> ContainsIndex<I> for HashSet<I> {
     fn contains_index(&self, index: &I) -> bool {
         self.contains(index)
@@ -51,3 +67,49 @@ impl<I: Index + Eq + Hash, V> ContainsIndex<I> for HashMap<I, V> {
         self.contains_key(index)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::{h3cellmap_from_sorted, H3CellMap};
+    use crate::H3Cell;
+
+    #[test]
+    fn cellmap_from_sorted_equals_collect() {
+        let mut cells = H3Cell::from_coordinate((12.3, 45.6).into(), 8)
+            .unwrap()
+            .grid_disk(10)
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+        // add some duplicates
+        cells.extend_from_slice(&cells.clone()[..20]);
+        cells.sort_unstable();
+
+        let items = cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (*cell, i))
+            .collect::<Vec<_>>();
+
+        let expected: H3CellMap<_> = items.iter().copied().collect();
+        let map = h3cellmap_from_sorted(items.iter().copied());
+        assert_eq!(map.len(), expected.len());
+        assert_eq!(map, expected);
+    }
+
+    #[test]
+    fn cellmap_from_unsorted_has_unique_keys() {
+        let cells = H3Cell::from_coordinate((12.3, 45.6).into(), 8)
+            .unwrap()
+            .grid_disk(1)
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+        let items = [(cells[0], 1), (cells[1], 2), (cells[0], 3)];
+
+        let expected: H3CellMap<_> = items.iter().copied().collect();
+        let map = h3cellmap_from_sorted(items.iter().copied());
+        assert_eq!(map.len(), 2);
+        assert_eq!(map, expected);
+    }
+}
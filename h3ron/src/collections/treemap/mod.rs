@@ -5,11 +5,20 @@ use std::marker::PhantomData;
 use roaring::RoaringTreemap;
 
 use crate::collections::ContainsIndex;
-use crate::Index;
+use crate::{H3Cell, Index};
 
 #[cfg(feature = "use-serde")]
 pub mod serde;
 
+/// number of h3 base cells
+const NUM_BASE_CELLS: usize = 122;
+
+/// offset of the base cell bits in the upper 32 bits of an h3 index
+const BASE_CELL_OFFSET_HIGH: u32 = 45 - 32;
+
+/// mask for the base cell bits
+const BASE_CELL_MASK: u32 = 0b111_1111;
+
 /// wrapper around [`roaring::RoaringTreemap`] to store h3 data.
 ///
 /// The implementation of `roaring::RoaringTreemap` splits `u64` into two
@@ -98,6 +107,19 @@ where
         self.treemap.is_superset(&rhs.treemap)
     }
 
+    /// Retains only the elements for which the predicate `f` returns true.
+    ///
+    /// `RoaringTreemap` provides no `retain`, so this collects the elements
+    /// to remove before removing them from the treemap.
+    pub fn retain<F: Fn(&T) -> bool>(&mut self, f: F) {
+        let to_remove: RoaringTreemap = self
+            .iter()
+            .filter(|index| !f(index))
+            .map(|index| index.h3index())
+            .collect();
+        self.treemap -= to_remove;
+    }
+
     pub fn iter(&self) -> Iter<T> {
         Iter {
             inner_iter: self.treemap.iter(),
@@ -105,6 +127,15 @@ where
         }
     }
 
+    /// Collect the contained indexes into a vec sorted ascending by their h3index.
+    ///
+    /// The treemap is iterated in sorted order, so no additional sorting is required.
+    pub fn to_sorted_vec(&self) -> Vec<T> {
+        let mut indexes = Vec::with_capacity(self.len());
+        indexes.extend(self.iter());
+        indexes
+    }
+
     /// create this struct from an iterator. The iterator is consumed and sorted in memory
     /// before creating the Treemap - this can greatly reduce the creation time.
     ///
@@ -145,6 +176,24 @@ where
     }
 }
 
+impl H3Treemap<H3Cell> {
+    /// Count the contained cells per base cell.
+    ///
+    /// The returned array is indexed by the base cell number. As the base cell is part of the
+    /// upper 32 bits of the h3 index, the counts are taken directly from the bitmaps of the
+    /// underlying `RoaringTreemap` without iterating over the individual cells.
+    pub fn cardinality_by_base_cell(&self) -> [u64; NUM_BASE_CELLS] {
+        let mut counts = [0u64; NUM_BASE_CELLS];
+        for (high_bits, bitmap) in self.treemap.bitmaps() {
+            let base_cell = ((high_bits >> BASE_CELL_OFFSET_HIGH) & BASE_CELL_MASK) as usize;
+            if let Some(count) = counts.get_mut(base_cell) {
+                *count += bitmap.len();
+            }
+        }
+        counts
+    }
+}
+
 impl<I: Index> ContainsIndex<I> for H3Treemap<I> {
     fn contains_index(&self, index: &I) -> bool {
         self.contains(index)
@@ -173,7 +222,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::H3Cell;
+    use crate::{res0_cells, H3Cell, Index};
 
     use super::H3Treemap;
 
@@ -186,4 +235,66 @@ mod tests {
         }
         assert_eq!(treemap.iter().count(), 7);
     }
+
+    #[test]
+    fn to_sorted_vec() {
+        let treemap: H3Treemap<H3Cell> = H3Cell::try_from(0x89283080ddbffff_u64)
+            .unwrap()
+            .grid_disk(5)
+            .unwrap()
+            .iter()
+            .collect();
+        let cells = treemap.to_sorted_vec();
+        assert_eq!(cells.len(), treemap.len());
+        assert!(cells.windows(2).all(|w| w[0].h3index() < w[1].h3index()));
+        assert!(H3Treemap::<H3Cell>::default().to_sorted_vec().is_empty());
+    }
+
+    #[test]
+    fn cardinality_by_base_cell() {
+        let cell = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
+        let treemap: H3Treemap<_> = cell.grid_disk(5).unwrap().iter().collect();
+        assert!(treemap
+            .iter()
+            .all(|c| c.get_base_cell_number() == cell.get_base_cell_number()));
+
+        let counts = treemap.cardinality_by_base_cell();
+        assert_eq!(counts.iter().filter(|count| **count > 0).count(), 1);
+        assert_eq!(
+            counts[cell.get_base_cell_number() as usize],
+            treemap.len() as u64
+        );
+
+        let all_children: H3Treemap<_> = res0_cells()
+            .iter()
+            .flat_map(|cell| cell.get_children(2).unwrap().iter().collect::<Vec<_>>())
+            .collect();
+        let counts = all_children.cardinality_by_base_cell();
+        for base_cell in res0_cells().iter() {
+            assert_eq!(
+                counts[base_cell.get_base_cell_number() as usize],
+                base_cell.children_count(2).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn retain() {
+        let mut treemap: H3Treemap<_> = res0_cells()
+            .iter()
+            .flat_map(|cell| cell.get_children(1).unwrap().iter().collect::<Vec<_>>())
+            .collect();
+        let num_even = treemap
+            .iter()
+            .filter(|cell| cell.get_base_cell_number() % 2 == 0)
+            .count();
+        assert!(num_even > 0);
+        assert!(num_even < treemap.len());
+
+        treemap.retain(|cell| cell.get_base_cell_number() % 2 == 0);
+        assert_eq!(treemap.len(), num_even);
+        assert!(treemap
+            .iter()
+            .all(|cell| cell.get_base_cell_number() % 2 == 0));
+    }
 }
@@ -127,6 +127,22 @@ impl H3DirectedEdge {
         Ok(res)
     }
 
+    /// Retrieves the edge between the parent cells of the origin and destination
+    /// cells at the coarser `parent_resolution`.
+    ///
+    /// # Returns
+    /// When both cells share the same parent, there is no edge at `parent_resolution`
+    /// and [`Error::NotNeighbors`] is returned.
+    pub fn get_parent(&self, parent_resolution: u8) -> Result<Self, Error> {
+        let edge_cells = self.cells()?;
+        let parent_origin = edge_cells.origin.get_parent(parent_resolution)?;
+        let parent_destination = edge_cells.destination.get_parent(parent_resolution)?;
+        if parent_origin == parent_destination {
+            return Err(Error::NotNeighbors);
+        }
+        parent_origin.directed_edge_to(parent_destination)
+    }
+
     /// Retrieves the corresponding edge in the reversed direction.
     ///
     /// # Returns
@@ -136,6 +152,14 @@ impl H3DirectedEdge {
         edge_cells.destination.directed_edge_to(edge_cells.origin)
     }
 
+    /// A key identifying the undirected edge between the two cells of `self`.
+    ///
+    /// The key is the smaller h3index of `self` and its [`H3DirectedEdge::reversed`]
+    /// edge, so both directions share the same key.
+    pub fn undirected_key(&self) -> Result<u64, Error> {
+        Ok(self.h3index().min(self.reversed()?.h3index()))
+    }
+
     /// Retrieves the [`LineString`] which forms the boundary between
     /// two cells.
     pub fn boundary_linestring(&self) -> Result<LineString<f64>, Error> {
@@ -379,6 +403,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn undirected_key() {
+        let cell = H3Cell::from_coordinate((12.0, 45.0).into(), 9).unwrap();
+        let pentagon = H3Cell::new(0x85080003fffffff);
+        for edge in cell
+            .directed_edges()
+            .unwrap()
+            .iter()
+            .chain(pentagon.directed_edges().unwrap().iter())
+        {
+            let rev_edge = edge.reversed().unwrap();
+            let key = edge.undirected_key().unwrap();
+            assert_eq!(key, rev_edge.undirected_key().unwrap());
+            assert!(key == edge.h3index() || key == rev_edge.h3index());
+        }
+
+        let edges = cell.directed_edges().unwrap();
+        let mut keys = edges
+            .iter()
+            .map(|edge| edge.undirected_key().unwrap())
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys.dedup();
+        assert_eq!(keys.len(), edges.count());
+    }
+
+    #[test]
+    fn get_parent() {
+        let cell = H3Cell::from_coordinate((12.0, 45.0).into(), 9).unwrap();
+        let parent = cell.get_parent(7).unwrap();
+
+        let mut n_crossing = 0;
+        let mut n_within = 0;
+        for neighbor_cell in cell.grid_disk(10).unwrap().iter() {
+            for edge in neighbor_cell.directed_edges().unwrap().iter() {
+                let edge_cells = edge.cells().unwrap();
+                if edge_cells.origin.get_parent(7).unwrap() != parent {
+                    continue;
+                }
+                match edge.get_parent(7) {
+                    Ok(parent_edge) => {
+                        n_crossing += 1;
+                        assert_eq!(parent_edge.resolution(), 7);
+                        assert_eq!(parent_edge.origin_cell().unwrap(), parent);
+                        assert_eq!(
+                            parent_edge.destination_cell().unwrap(),
+                            edge_cells.destination.get_parent(7).unwrap()
+                        );
+                    }
+                    Err(e) => {
+                        n_within += 1;
+                        assert!(matches!(e, Error::NotNeighbors));
+                        assert_eq!(edge_cells.destination.get_parent(7).unwrap(), parent);
+                    }
+                }
+            }
+        }
+        assert!(n_crossing > 0);
+        assert!(n_within > 0);
+    }
+
     #[test]
     fn boundary_linestring() {
         let edge = H3DirectedEdge::new(0x149283080ddbffff);
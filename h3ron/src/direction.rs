@@ -96,6 +96,39 @@ impl H3Direction {
         direction(index.h3index(), offset(target_resolution))
     }
 
+    /// Short name of the direction, matching the digit names used by H3.
+    ///
+    /// ```
+    /// use h3ron::H3Direction;
+    ///
+    /// assert_eq!(H3Direction::JkAxesDigit.name(), "jk");
+    /// ```
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::CenterDigit => "center",
+            Self::KAxesDigit => "k",
+            Self::JAxesDigit => "j",
+            Self::JkAxesDigit => "jk",
+            Self::IAxesDigit => "i",
+            Self::IkAxesDigit => "ik",
+            Self::IjAxesDigit => "ij",
+        }
+    }
+
+    /// Direction from its name as returned by [`H3Direction::name`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "center" => Self::CenterDigit,
+            "k" => Self::KAxesDigit,
+            "j" => Self::JAxesDigit,
+            "jk" => Self::JkAxesDigit,
+            "i" => Self::IAxesDigit,
+            "ik" => Self::IkAxesDigit,
+            "ij" => Self::IjAxesDigit,
+            _ => return None,
+        })
+    }
+
     /// iterate over all directions leading to the given `index` starting from
     /// resolution 0 to the resolution of the `index`.
     pub fn iter_directions_over_resolutions<I: Index>(index: &I) -> ResolutionDirectionIter {
@@ -155,6 +188,15 @@ mod tests {
         assert_eq!(H3Direction::try_from(6).unwrap(), H3Direction::IjAxesDigit);
     }
 
+    #[test]
+    fn name_roundtrip() {
+        for value in 0..7_u8 {
+            let direction = H3Direction::try_from(value).unwrap();
+            assert_eq!(H3Direction::from_name(direction.name()), Some(direction));
+        }
+        assert_eq!(H3Direction::from_name("x"), None);
+    }
+
     #[test]
     fn can_be_compared() {
         assert!(H3Direction::CenterDigit < H3Direction::KAxesDigit);
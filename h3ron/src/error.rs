@@ -77,6 +77,12 @@ pub enum Error {
 
     #[error("decompression error")]
     DecompressionError(String),
+
+    #[error("invalid WKT: {0}")]
+    InvalidWkt(String),
+
+    #[error("io error: {0}")]
+    IOError(#[from] std::io::Error),
 }
 
 impl Error {
@@ -34,6 +34,16 @@ impl CellBoundaryBuilder {
         })?;
         Ok(CellBoundaryIter::new(&self.cell_boundary, close_ring))
     }
+
+    /// write the closed exterior ring of the polygon of `cell` into `out`.
+    ///
+    /// `out` is cleared before, so its allocation can be reused when converting large numbers
+    /// of cells. The coordinates equal the exterior of `ToPolygon::to_polygon`.
+    pub fn polygon_into(&mut self, cell: &H3Cell, out: &mut Vec<Coord<f64>>) -> Result<(), Error> {
+        out.clear();
+        out.extend(self.iter_cell_boundary_vertices(cell, true)?);
+        Ok(())
+    }
 }
 
 impl Default for CellBoundaryBuilder {
@@ -109,3 +119,24 @@ impl<'b> From<CellBoundaryIter<'b>> for Polygon<f64> {
         Self::new(LineString::from(exterior), Vec::with_capacity(0))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use geo_types::Coord;
+
+    use crate::iter::CellBoundaryBuilder;
+    use crate::{H3Cell, Index, ToPolygon};
+
+    #[test]
+    fn polygon_into_reused_buffer() {
+        let mut builder = CellBoundaryBuilder::new();
+        let mut buf: Vec<Coord<f64>> = Vec::new();
+
+        let cell = H3Cell::from_coordinate((12.3, 45.6).into(), 8).unwrap();
+        let pentagon = H3Cell::new(0x85080003fffffff);
+        for cell in cell.grid_disk(2).unwrap().iter().chain([pentagon]) {
+            builder.polygon_into(&cell, &mut buf).unwrap();
+            assert_eq!(buf, cell.to_polygon().unwrap().exterior().0);
+        }
+    }
+}
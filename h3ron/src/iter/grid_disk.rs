@@ -2,6 +2,7 @@ use std::os::raw::c_int;
 
 use h3ron_h3_sys::H3Index;
 
+use crate::collections::H3CellSet;
 use crate::{max_grid_disk_size, Error, H3Cell, Index};
 
 /// `GridDiskBuilder` allows building k-rings with allocations only on the creation
@@ -44,6 +45,23 @@ impl GridDiskBuilder {
         })
     }
 
+    /// Change the radius of the grid disks built by this builder.
+    ///
+    /// The internal buffers only get re-allocated when the new `k_max` requires more
+    /// space than is already available. Any remaining items of the iterator are discarded.
+    pub fn set_k_range(&mut self, k_min: u32, k_max: u32) -> Result<(), Error> {
+        let k_ring_size = max_grid_disk_size(k_max)?;
+        if k_ring_size > self.k_ring_indexes.len() {
+            self.k_ring_indexes.resize(k_ring_size, 0);
+            self.k_ring_distances.resize(k_ring_size, 0);
+        }
+        self.k_min = k_min;
+        self.k_max = k_max;
+        self.k_ring_size = k_ring_size;
+        self.current_pos = k_ring_size; // nothing left to iterate over
+        Ok(())
+    }
+
     #[inline(always)]
     fn rewind_iterator(&mut self) {
         self.current_pos = 0;
@@ -101,3 +119,150 @@ impl Iterator for GridDiskBuilder {
         (self.k_ring_size - self.current_pos, None)
     }
 }
+
+/// Iterator over the cells of a grid disk generated ring by ring.
+///
+/// In contrast to [`H3Cell::grid_disk`] only the current and the previous ring are held
+/// in memory, which allows to iterate over disks with very large `k`.
+/// Created by [`H3Cell::grid_disk_iter`].
+pub struct GridDiskIter {
+    center: H3Cell,
+    k_max: u32,
+
+    /// the `k` of the next ring to generate
+    next_k: u32,
+
+    previous_ring: Vec<H3Cell>,
+    current_ring: Vec<H3Cell>,
+    current_pos: usize,
+    failed: bool,
+}
+
+impl GridDiskIter {
+    pub(crate) const fn new(center: H3Cell, k_max: u32) -> Self {
+        Self {
+            center,
+            k_max,
+            next_k: 0,
+            previous_ring: Vec::new(),
+            current_ring: Vec::new(),
+            current_pos: 0,
+            failed: false,
+        }
+    }
+
+    fn build_next_ring(&self) -> Result<Vec<H3Cell>, Error> {
+        if self.next_k == 0 {
+            return Ok(vec![self.center]);
+        }
+
+        let mut ring: Vec<H3Index> = vec![0; 6 * self.next_k as usize];
+        let returncode = unsafe {
+            h3ron_h3_sys::gridRingUnsafe(
+                self.center.h3index(),
+                self.next_k as c_int,
+                ring.as_mut_ptr(),
+            )
+        };
+        if !Error::is_error(returncode) {
+            return Ok(ring.into_iter().map(H3Cell::new).collect());
+        }
+
+        // the ring is affected by pentagon distortion. Fall back to traversing
+        // the neighbors of the current ring, as done by libh3 for `gridDisk`.
+        let mut seen: H3CellSet = self
+            .previous_ring
+            .iter()
+            .chain(self.current_ring.iter())
+            .copied()
+            .collect();
+        let mut ring = Vec::with_capacity(6 * self.next_k as usize);
+        for cell in self.current_ring.iter() {
+            for neighbor in cell.grid_disk(1)?.iter() {
+                if seen.insert(neighbor) {
+                    ring.push(neighbor);
+                }
+            }
+        }
+        Ok(ring)
+    }
+}
+
+impl Iterator for GridDiskIter {
+    type Item = Result<H3Cell, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(cell) = self.current_ring.get(self.current_pos) {
+                self.current_pos += 1;
+                return Some(Ok(*cell));
+            }
+            if self.failed || self.next_k > self.k_max {
+                return None;
+            }
+            match self.build_next_ring() {
+                Ok(ring) => {
+                    self.previous_ring = std::mem::replace(&mut self.current_ring, ring);
+                    self.current_pos = 0;
+                    self.next_k += 1;
+                }
+                Err(e) => {
+                    self.failed = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::iter::GridDiskBuilder;
+    use crate::{H3Cell, Index};
+
+    #[test]
+    fn set_k_range_varying_k() {
+        let cell = H3Cell::from_coordinate((12.0, 45.0).into(), 7).unwrap();
+        let mut builder = GridDiskBuilder::create(0, 1).unwrap();
+
+        for (k_min, k_max) in [(0, 3), (1, 1), (2, 4), (0, 0), (3, 3)] {
+            builder.set_k_range(k_min, k_max).unwrap();
+            let mut built = builder
+                .build_grid_disk(&cell)
+                .unwrap()
+                .map(|(c, _)| c)
+                .collect::<Vec<_>>();
+            built.sort_unstable();
+
+            let mut expected = cell
+                .grid_disk_distances(k_min, k_max)
+                .unwrap()
+                .into_iter()
+                .map(|(_, c)| c)
+                .collect::<Vec<_>>();
+            expected.sort_unstable();
+
+            assert_eq!(built, expected);
+        }
+    }
+
+    #[test]
+    fn grid_disk_iter_equals_grid_disk() {
+        let hexagon = H3Cell::from_coordinate((12.0, 45.0).into(), 7).unwrap();
+        let pentagon = H3Cell::new(0x85080003fffffff);
+        for center in [hexagon, pentagon] {
+            for k in [0, 1, 5, 12] {
+                let mut streamed = center
+                    .grid_disk_iter(k)
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap();
+                streamed.sort_unstable();
+
+                let mut expected = center.grid_disk(k).unwrap().iter().collect::<Vec<_>>();
+                expected.sort_unstable();
+
+                assert_eq!(streamed, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{CoordIj, Error, H3Cell};
+
+/// Iterator over the cells of the line connecting two cells. The cells are computed one
+/// at a time instead of being allocated all at once.
+///
+/// This follows the same algorithm as [`crate::grid_path_cells`] by interpolating
+/// between the cube coordinates of both cells in the local IJ coordinate space
+/// anchored at the start cell.
+///
+/// Created by [`crate::grid_path_cells_iter`].
+pub struct GridPathCellsIter {
+    origin_cell: H3Cell,
+    start_cube: [f64; 3],
+    step: [f64; 3],
+    distance: u32,
+    n: u32,
+}
+
+/// convert local IJ coordinates to cube coordinates
+const fn ij_to_cube(coordij: CoordIj) -> [i32; 3] {
+    [-coordij.i, coordij.j, coordij.i - coordij.j]
+}
+
+/// round fractional cube coordinates to the nearest valid cube coordinate
+fn cube_round(cube: [f64; 3]) -> [i32; 3] {
+    let mut rounded = cube.map(|v| v.round() as i32);
+    let diff = [0, 1, 2].map(|i| (f64::from(rounded[i]) - cube[i]).abs());
+
+    // round, maintaining valid cube coordinates
+    if diff[0] > diff[1] && diff[0] > diff[2] {
+        rounded[0] = -rounded[1] - rounded[2];
+    } else if diff[1] > diff[2] {
+        rounded[1] = -rounded[0] - rounded[2];
+    } else {
+        rounded[2] = -rounded[0] - rounded[1];
+    }
+    rounded
+}
+
+impl GridPathCellsIter {
+    /// Fails when no line can be computed between `start` and `end` - for example when they are
+    /// too far apart or located on opposite sides of a pentagon.
+    pub(crate) fn new(start: H3Cell, end: H3Cell) -> Result<Self, Error> {
+        let start_cube = ij_to_cube(start.to_localij(start)?);
+        let end_cube = ij_to_cube(end.to_localij(start)?);
+
+        let distance = (0..3)
+            .map(|i| (end_cube[i] - start_cube[i]).unsigned_abs())
+            .max()
+            .unwrap_or(0);
+        let step = [0, 1, 2].map(|i| {
+            if distance == 0 {
+                0.0
+            } else {
+                f64::from(end_cube[i] - start_cube[i]) / f64::from(distance)
+            }
+        });
+
+        Ok(Self {
+            origin_cell: start,
+            start_cube: start_cube.map(f64::from),
+            step,
+            distance,
+            n: 0,
+        })
+    }
+}
+
+impl Iterator for GridPathCellsIter {
+    type Item = Result<H3Cell, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.n > self.distance {
+            return None;
+        }
+        let n = f64::from(self.n);
+        // same order of operations as libh3 to get identical rounding
+        let cube = cube_round([0, 1, 2].map(|i| self.start_cube[i] + self.step[i] * n));
+        self.n += 1;
+
+        let cell = H3Cell::from_localij(
+            self.origin_cell,
+            CoordIj {
+                i: -cube[0],
+                j: cube[1],
+            },
+        );
+        if cell.is_err() {
+            // cells falling in pentagon distortion end the line
+            self.n = self.distance + 1;
+        }
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.distance + 1).saturating_sub(self.n) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{grid_path_cells, grid_path_cells_iter, H3Cell};
+
+    #[test]
+    fn equals_grid_path_cells() {
+        let center = H3Cell::from_coordinate((12.3, 45.6).into(), 7).unwrap();
+        let starts = center.grid_disk(2).unwrap();
+        for start in starts.iter() {
+            for end in center.grid_ring_unsafe(8).unwrap().iter() {
+                let expected: Vec<_> = grid_path_cells(start, end).unwrap().iter().collect();
+                let iter = grid_path_cells_iter(start, end).unwrap();
+                assert_eq!(iter.size_hint(), (expected.len(), Some(expected.len())));
+                let streamed = iter.collect::<Result<Vec<_>, _>>().unwrap();
+                assert_eq!(streamed, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn single_cell() {
+        let cell = H3Cell::from_coordinate((12.3, 45.6).into(), 7).unwrap();
+        let cells = grid_path_cells_iter(cell, cell)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(cells, vec![cell]);
+    }
+
+    #[test]
+    fn fails_up_front_across_faces() {
+        let start = H3Cell::from_coordinate((12.3, 45.6).into(), 7).unwrap();
+        let end = H3Cell::from_coordinate((-120.0, -30.0).into(), 7).unwrap();
+        assert!(grid_path_cells(start, end).is_err());
+        assert!(grid_path_cells_iter(start, end).is_err());
+    }
+}
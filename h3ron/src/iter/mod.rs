@@ -11,6 +11,8 @@
 //! # Grid traversal
 //!
 //! * [`GridDiskBuilder`]
+//! * [`GridDiskIter`]
+//! * [`GridPathCellsIter`]
 //! * [`neighbors_within_distance_window_or_default`]
 //! * [`neighbors_within_distance_window`]
 //! * [`neighbors_within_distance`]
@@ -28,12 +30,14 @@
 
 pub use boundary::{CellBoundaryBuilder, CellBoundaryIter};
 pub use edge::{continuous_cells_to_edges, CellsToEdgesIter, H3DirectedEdgesBuilder};
-pub use grid_disk::GridDiskBuilder;
+pub use grid_disk::{GridDiskBuilder, GridDiskIter};
+pub use grid_path::GridPathCellsIter;
 pub use neighbor::*;
 pub use resolution::{change_resolution, change_resolution_tuple};
 
 mod boundary;
 mod edge;
 mod grid_disk;
+mod grid_path;
 mod neighbor;
 mod resolution;
@@ -4,9 +4,11 @@
 //! # Features
 //!
 //! * **use-serde**: serde serialization/deserialization for most types of this crate.
-//! * **use-rayon**
+//! * **use-rayon**: Parallelize some of the batch functions like [`parents_of`].
 //! * **roaring**: Enables `collections::H3Treemap` based on the `roaring` crate.
 //! * **parse**: Parse [`H3Cell`] from different string representations using `H3Cell::from_str`.
+//! * **wkt**: Create [`H3Cell`] from WKT points using `H3Cell::from_wkt_point`.
+//! * **s2**: Approximate [`H3Cell`] with S2 cells using `H3Cell::to_s2_cell_id`.
 //!
 #![warn(nonstandard_style)]
 #![allow(clippy::redundant_pub_crate)]
@@ -16,7 +18,8 @@ use geo_types::LineString;
 
 use h3ron_h3_sys::H3Index;
 pub use to_geo::{
-    to_linked_polygons, ToAlignedLinkedPolygons, ToCoordinate, ToLinkedPolygons, ToPolygon,
+    cells_to_dissolved_multipolygon, compacted_to_multipolygon, to_linked_polygons,
+    ToAlignedLinkedPolygons, ToCoordinate, ToLinkedPolygons, ToPolygon,
 };
 pub use {
     cell::H3Cell,
@@ -26,10 +29,16 @@ pub use {
     index::HasH3Resolution,
     index::Index,
     localij::CoordIj,
-    to_h3::{ToH3Cells, ToIntersectingH3Cells},
+    to_h3::{ContainmentMode, ToH3Cells, ToIntersectingH3Cells},
+    vertex::H3Vertex,
 };
 
 use crate::collections::indexvec::IndexVec;
+use crate::collections::H3CellSet;
+#[cfg(feature = "roaring")]
+use crate::collections::H3Treemap;
+use crate::error::check_valid_h3_resolution;
+use crate::iter::GridPathCellsIter;
 
 #[macro_use]
 pub mod algorithm;
@@ -41,8 +50,12 @@ pub mod error;
 mod index;
 pub mod iter;
 pub mod localij;
+#[cfg(feature = "use-serde")]
+pub mod serialize_hex;
 pub mod to_geo;
 pub mod to_h3;
+mod vertex;
+pub mod xyz_tile;
 
 pub const H3_MIN_RESOLUTION: u8 = 0_u8;
 pub const H3_MAX_RESOLUTION: u8 = 15_u8;
@@ -75,6 +88,129 @@ pub fn compact_cells(cells: &[H3Cell]) -> Result<IndexVec<H3Cell>, Error> {
     Ok(index_vec)
 }
 
+/// uncompact the cells to `h3_resolution` directly into a set.
+///
+/// In contrast to collecting the result of uncompacting to an `IndexVec`, the children
+/// are generated one by one, so no intermediate buffer for all cells is required.
+/// All cells must be at `h3_resolution` or coarser.
+pub fn uncompact_into_set(compacted: &[H3Cell], h3_resolution: u8) -> Result<H3CellSet, Error> {
+    check_valid_h3_resolution(h3_resolution)?;
+    let mut set = H3CellSet::default();
+    for cell in compacted {
+        let children_count = cell.children_count(h3_resolution)?;
+        set.reserve(children_count as usize);
+        for child_pos in 0..children_count {
+            let mut child_h3index: H3Index = 0;
+            Error::check_returncode(unsafe {
+                h3ron_h3_sys::childPosToCell(
+                    child_pos as i64,
+                    cell.h3index(),
+                    i32::from(h3_resolution),
+                    &mut child_h3index,
+                )
+            })?;
+            set.insert(H3Cell::new(child_h3index));
+        }
+    }
+    Ok(set)
+}
+
+/// The parent at `parent_resolution` of each of the `cells`.
+///
+/// The returned parents are in the same order as the `cells`. Runs in parallel
+/// when the `use-rayon` feature is enabled.
+pub fn parents_of(cells: &[H3Cell], parent_resolution: u8) -> Result<Vec<H3Cell>, Error> {
+    #[cfg(feature = "use-rayon")]
+    {
+        use rayon::prelude::*;
+        cells
+            .par_iter()
+            .map(|cell| cell.get_parent(parent_resolution))
+            .collect()
+    }
+
+    #[cfg(not(feature = "use-rayon"))]
+    {
+        cells
+            .iter()
+            .map(|cell| cell.get_parent(parent_resolution))
+            .collect()
+    }
+}
+
+/// The sum of the exact lengths of the `edges` in meters.
+///
+/// Runs in parallel when the `use-rayon` feature is enabled.
+pub fn total_edge_length_m(edges: &[H3DirectedEdge]) -> Result<f64, Error> {
+    #[cfg(feature = "use-rayon")]
+    {
+        use rayon::prelude::*;
+        edges.par_iter().map(H3DirectedEdge::length_m).sum()
+    }
+
+    #[cfg(not(feature = "use-rayon"))]
+    {
+        edges.iter().map(H3DirectedEdge::length_m).sum()
+    }
+}
+
+/// The resolution whose average hexagon area is closest to `target_area_m2`.
+///
+/// As the area of the cells grows by a factor of about 7 with each coarser resolution,
+/// the areas are compared on a logarithmic scale. Targets outside of the range
+/// of areas covered by H3 return the finest or coarsest resolution.
+pub fn resolution_for_area_m2(target_area_m2: f64) -> u8 {
+    let target_log = target_area_m2.max(f64::MIN_POSITIVE).ln();
+    (0..=H3_MAX_RESOLUTION)
+        .map(|h3_resolution| {
+            // all resolutions in this range are valid
+            let area_m2 = H3Cell::area_avg_m2(h3_resolution).unwrap_or(f64::NAN);
+            (h3_resolution, (area_m2.ln() - target_log).abs())
+        })
+        .min_by(|(_, diff_a), (_, diff_b)| diff_a.total_cmp(diff_b))
+        .map(|(h3_resolution, _)| h3_resolution)
+        .unwrap_or(H3_MAX_RESOLUTION)
+}
+
+/// Strategy used to pick a resolution matching a target size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResolutionSearchMode {
+    /// Choose the resolution where the difference to the target size is as small as possible.
+    MinDiff,
+
+    /// Choose the coarsest resolution where the size of the cells is smaller than the target size -
+    /// for example the size of a pixel.
+    SmallerThanPixel,
+}
+
+/// The resolution whose average hexagon edge length matches `target_edge_m` according
+/// to `mode`.
+///
+/// Targets outside of the range of edge lengths covered by H3 return the finest or
+/// coarsest resolution.
+pub fn resolution_for_edge_length_m(target_edge_m: f64, mode: ResolutionSearchMode) -> u8 {
+    // all resolutions in this range are valid
+    let mut edge_lengths = (0..=H3_MAX_RESOLUTION).map(|h3_resolution| {
+        (
+            h3_resolution,
+            H3DirectedEdge::edge_length_avg_m(h3_resolution).unwrap_or(f64::NAN),
+        )
+    });
+    match mode {
+        ResolutionSearchMode::MinDiff => edge_lengths
+            .min_by(|(_, length_a), (_, length_b)| {
+                (length_a - target_edge_m)
+                    .abs()
+                    .total_cmp(&(length_b - target_edge_m).abs())
+            })
+            .map(|(h3_resolution, _)| h3_resolution),
+        ResolutionSearchMode::SmallerThanPixel => edge_lengths
+            .find(|(_, edge_length)| *edge_length <= target_edge_m)
+            .map(|(h3_resolution, _)| h3_resolution),
+    }
+    .unwrap_or(H3_MAX_RESOLUTION)
+}
+
 /// maximum number of cells needed for the `k_ring`
 pub fn max_grid_disk_size(k: u32) -> Result<usize, Error> {
     let mut max_size: i64 = 0;
@@ -82,6 +218,21 @@ pub fn max_grid_disk_size(k: u32) -> Result<usize, Error> {
     Ok(max_size as usize)
 }
 
+/// Union of the grid disks with radius `k` around all `centers`.
+///
+/// Cells covered by multiple disks are contained only once in the returned treemap.
+#[cfg(feature = "roaring")]
+pub fn grid_disk_union(centers: &[H3Cell], k: u32) -> Result<H3Treemap<H3Cell>, Error> {
+    let mut builder = iter::GridDiskBuilder::create(0, k)?;
+    let mut treemap = H3Treemap::default();
+    for center in centers {
+        for (cell, _) in builder.build_grid_disk(center)? {
+            treemap.insert(cell);
+        }
+    }
+    Ok(treemap)
+}
+
 /// Number of cells in a line connecting two cells
 pub fn grid_path_cells_size(start: H3Cell, end: H3Cell) -> Result<usize, Error> {
     let mut cells_size: i64 = 0;
@@ -109,6 +260,20 @@ pub fn grid_path_cells(start: H3Cell, end: H3Cell) -> Result<IndexVec<H3Cell>, E
     Ok(index_vec)
 }
 
+/// Lazily computed line of h3 indexes connecting two cells.
+///
+/// In contrast to [`grid_path_cells`] the cells are computed one at a time while iterating,
+/// so no memory for the whole line needs to be allocated.
+///
+/// # Errors
+///
+/// Fails up front when the line can not be computed - for example when the cells
+/// are located on different icosahedron faces too far apart. Cells falling into the distortion
+/// of a pentagon are reported as errors during the iteration, which then ends.
+pub fn grid_path_cells_iter(start: H3Cell, end: H3Cell) -> Result<GridPathCellsIter, Error> {
+    GridPathCellsIter::new(start, end)
+}
+
 /// Generate h3 cells along the given linestring
 ///
 /// The returned cells are ordered sequentially, there are no
@@ -147,7 +312,11 @@ pub fn res0_cells() -> IndexVec<H3Cell> {
 mod tests {
     use geo_types::{Coord, LineString};
 
-    use crate::{grid_path_cells, line, res0_cell_count, res0_cells, H3Cell};
+    use crate::{
+        grid_path_cells, line, parents_of, res0_cell_count, res0_cells, resolution_for_area_m2,
+        resolution_for_edge_length_m, total_edge_length_m, uncompact_into_set, H3Cell,
+        H3DirectedEdge, Index, ResolutionSearchMode, H3_MAX_RESOLUTION,
+    };
 
     #[test]
     fn line_across_multiple_faces() {
@@ -159,6 +328,118 @@ mod tests {
         assert!(grid_path_cells(start, end).is_err());
     }
 
+    #[test]
+    fn uncompact_into_set_matches_children() {
+        let cell = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
+        let parent = cell.get_parent(6).unwrap();
+        let pentagon = H3Cell::try_from(0x85080003fffffff_u64).unwrap();
+        let compacted = vec![cell, parent, pentagon];
+
+        let set = uncompact_into_set(&compacted, 9).unwrap();
+
+        let mut expected = compacted
+            .iter()
+            .flat_map(|c| c.get_children(9).unwrap().iter().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        expected.sort_unstable();
+        expected.dedup();
+
+        assert_eq!(set.len(), expected.len());
+        assert!(expected.iter().all(|c| set.contains(c)));
+
+        // cells finer than the target resolution
+        assert!(uncompact_into_set(&[cell], 8).is_err());
+    }
+
+    #[test]
+    fn parents_of_cells() {
+        let cells = H3Cell::try_from(0x89283080ddbffff_u64)
+            .unwrap()
+            .grid_disk(3)
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+
+        let parents = parents_of(&cells, 6).unwrap();
+        assert_eq!(parents.len(), cells.len());
+        for (cell, parent) in cells.iter().zip(parents.iter()) {
+            assert_eq!(parent.resolution(), 6);
+            assert!(cell.is_child_of(parent).unwrap());
+        }
+
+        // parent resolution finer than the cells
+        assert!(parents_of(&cells, 10).is_err());
+    }
+
+    #[test]
+    fn total_edge_length() {
+        let edges = H3Cell::try_from(0x89283080ddbffff_u64)
+            .unwrap()
+            .grid_disk(2)
+            .unwrap()
+            .iter()
+            .flat_map(|cell| cell.directed_edges().unwrap().iter().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(edges.len(), 19 * 6);
+
+        let total = total_edge_length_m(&edges).unwrap();
+        let avg_length = H3DirectedEdge::edge_length_avg_m(9).unwrap();
+        assert!(total > edges.len() as f64 * avg_length * 0.8);
+        assert!(total < edges.len() as f64 * avg_length * 1.2);
+
+        let sequential = edges
+            .iter()
+            .map(|edge| edge.length_m().unwrap())
+            .sum::<f64>();
+        assert!((total - sequential).abs() < 1e-6);
+
+        assert_eq!(total_edge_length_m(&[]).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn resolution_for_area() {
+        assert_eq!(resolution_for_area_m2(1_000_000.0), 8);
+        for h3_resolution in 0..=H3_MAX_RESOLUTION {
+            let area_m2 = H3Cell::area_avg_m2(h3_resolution).unwrap();
+            assert_eq!(resolution_for_area_m2(area_m2), h3_resolution);
+        }
+        assert_eq!(resolution_for_area_m2(0.0), H3_MAX_RESOLUTION);
+        assert_eq!(resolution_for_area_m2(1.0e20), 0);
+    }
+
+    #[test]
+    fn resolution_for_edge_length() {
+        // average edge lengths: res 7 ~1221m, res 8 ~461m, res 9 ~174m
+        assert_eq!(
+            resolution_for_edge_length_m(500.0, ResolutionSearchMode::MinDiff),
+            8
+        );
+        assert_eq!(
+            resolution_for_edge_length_m(500.0, ResolutionSearchMode::SmallerThanPixel),
+            8
+        );
+        assert_eq!(
+            resolution_for_edge_length_m(450.0, ResolutionSearchMode::MinDiff),
+            8
+        );
+        assert_eq!(
+            resolution_for_edge_length_m(450.0, ResolutionSearchMode::SmallerThanPixel),
+            9
+        );
+        for mode in [
+            ResolutionSearchMode::MinDiff,
+            ResolutionSearchMode::SmallerThanPixel,
+        ] {
+            let edge_length = H3DirectedEdge::edge_length_avg_m(5).unwrap();
+            assert_eq!(resolution_for_edge_length_m(edge_length, mode), 5);
+            assert_eq!(resolution_for_edge_length_m(0.0, mode), H3_MAX_RESOLUTION);
+        }
+        assert_eq!(
+            resolution_for_edge_length_m(1.0e9, ResolutionSearchMode::MinDiff),
+            0
+        );
+    }
+
     #[test]
     fn linestring() {
         let ls = LineString::from(vec![
@@ -171,6 +452,23 @@ mod tests {
         assert!(line(&ls, 5).unwrap().count() > 200);
     }
 
+    #[cfg(feature = "roaring")]
+    #[test]
+    fn grid_disk_union_of_nearby_centers() {
+        let center1 = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
+        let center2 = center1.grid_ring_unsafe(2).unwrap().first().unwrap();
+        let k = 3;
+
+        let union = crate::grid_disk_union(&[center1, center2], k).unwrap();
+        let disk1 = center1.grid_disk(k).unwrap();
+        let disk2 = center2.grid_disk(k).unwrap();
+        assert!(union.len() < disk1.count() + disk2.count());
+        assert!(disk1
+            .iter()
+            .chain(disk2.iter())
+            .all(|cell| union.contains(&cell)));
+    }
+
     #[test]
     fn test_res0_index_count() {
         assert_eq!(res0_cell_count(), 122);
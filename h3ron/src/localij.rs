@@ -97,6 +97,18 @@ impl H3Cell {
         })?;
         Ok(cij.into())
     }
+
+    /// Integer grid coordinates of this [`H3Cell`] as `(row, col)` tuple relative to `origin_cell`.
+    ///
+    /// These are the [`CoordIj`] coordinates of [`H3Cell::to_localij`] shifted so
+    /// that `origin_cell` is located at `(0, 0)`. This allows addressing cells in arrays when
+    /// working on small regions.
+    ///
+    /// The same limitations as for [`H3Cell::to_localij`] apply.
+    pub fn to_local_grid(&self, origin_cell: Self) -> Result<(i32, i32), Error> {
+        let coordij = self.to_localij(origin_cell)? - origin_cell.to_localij(origin_cell)?;
+        Ok((coordij.i, coordij.j))
+    }
 }
 
 #[cfg(test)]
@@ -126,4 +138,23 @@ mod tests {
         let other_cell_2 = H3Cell::from_localij(origin_cell, coordij_other).unwrap();
         assert_eq!(other_cell, other_cell_2);
     }
+
+    #[test]
+    fn test_local_grid() {
+        let origin_cell = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
+        assert_eq!(origin_cell.to_local_grid(origin_cell).unwrap(), (0, 0));
+
+        let mut offsets = origin_cell
+            .grid_ring_unsafe(1)
+            .unwrap()
+            .iter()
+            .map(|cell| cell.to_local_grid(origin_cell).unwrap())
+            .collect::<Vec<_>>();
+        for (row, col) in offsets.iter() {
+            assert_eq!(row.abs().max(col.abs()), 1);
+        }
+        offsets.sort_unstable();
+        offsets.dedup();
+        assert_eq!(offsets.len(), 6);
+    }
 }
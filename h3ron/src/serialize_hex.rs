@@ -0,0 +1,35 @@
+//! Serialize [`Index`] types like [`crate::H3Cell`] as hexadecimal strings instead of integers.
+//!
+//! To be used with the `with` attribute of serde:
+//!
+//! ```
+//! use h3ron::H3Cell;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Location {
+//!     #[serde(with = "h3ron::serialize_hex")]
+//!     cell: H3Cell,
+//! }
+//! ```
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serializer};
+
+use crate::index::{index_from_str, Index};
+
+pub fn serialize<IX, S>(index: &IX, serializer: S) -> Result<S::Ok, S::Error>
+where
+    IX: Index,
+    S: Serializer,
+{
+    serializer.serialize_str(&format!("{:x}", index.h3index()))
+}
+
+pub fn deserialize<'de, IX, D>(deserializer: D) -> Result<IX, D::Error>
+where
+    IX: Index,
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    index_from_str(&s).map_err(D::Error::custom)
+}
@@ -2,7 +2,7 @@ use crate::collections::H3CellMap;
 use std::os::raw::c_int;
 
 use geo::algorithm::euclidean_distance::EuclideanDistance;
-use geo_types::{Coord, Line, LineString, MultiLineString, Point, Polygon};
+use geo_types::{Coord, Line, LineString, MultiLineString, MultiPolygon, Point, Polygon};
 
 use h3ron_h3_sys::H3Index;
 
@@ -243,11 +243,39 @@ pub fn to_linked_polygons(cells: &[H3Cell], smoothen: bool) -> Result<Vec<Polygo
     }
 }
 
+/// dissolve the cells into the outline of the area they cover
+///
+/// Adjacent cells are merged into single polygons, holes are preserved as interiors. The
+/// cells do not need to be deduplicated, but all must be of the same resolution.
+pub fn cells_to_dissolved_multipolygon(
+    cells: &[H3Cell],
+    smoothen: bool,
+) -> Result<MultiPolygon<f64>, Error> {
+    let mut cells = cells.to_vec();
+    cells.sort_unstable();
+    cells.dedup();
+    to_linked_polygons(&cells, smoothen).map(MultiPolygon::new)
+}
+
+/// dissolve the cells of a [`CompactedCellVec`] into the outline of the area they cover
+///
+/// The cells get uncompacted to the finest resolution contained, so the boundaries between
+/// cells of different resolutions align exactly and do not leave gaps or slivers.
+pub fn compacted_to_multipolygon(
+    cv: &CompactedCellVec,
+    smoothen: bool,
+) -> Result<MultiPolygon<f64>, Error> {
+    cv.to_linked_polygons(smoothen).map(MultiPolygon::new)
+}
+
 #[cfg(test)]
 mod tests {
     use geo_types::Coord;
 
-    use crate::{H3Cell, ToLinkedPolygons};
+    use crate::collections::CompactedCellVec;
+    use crate::{
+        cells_to_dissolved_multipolygon, compacted_to_multipolygon, H3Cell, ToLinkedPolygons,
+    };
 
     #[test]
     fn donut_linked_polygon() {
@@ -261,4 +289,35 @@ mod tests {
         assert_eq!(polygons[0].interiors().len(), 1);
         assert_eq!(polygons[0].interiors()[0].0.len(), 7);
     }
+
+    #[test]
+    fn dissolve_filled_disk() {
+        let disk = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), 6)
+            .unwrap()
+            .grid_disk(3)
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+        let multipolygon = cells_to_dissolved_multipolygon(&disk, false).unwrap();
+        assert_eq!(multipolygon.0.len(), 1);
+        assert!(multipolygon.0[0].interiors().is_empty());
+    }
+
+    #[test]
+    fn compacted_disk_to_multipolygon() {
+        let disk = H3Cell::from_coordinate(Coord::from((23.3, 12.3)), 7)
+            .unwrap()
+            .grid_disk(12)
+            .unwrap();
+        let mut cv = CompactedCellVec::new();
+        cv.add_cells(disk.iter(), true).unwrap();
+        assert!(cv.len_resolutions().iter().filter(|n| **n > 0).count() > 1);
+
+        let multipolygon = compacted_to_multipolygon(&cv, false).unwrap();
+        assert_eq!(multipolygon.0.len(), 1);
+        assert!(multipolygon.0[0].interiors().is_empty());
+
+        let smoothed = compacted_to_multipolygon(&cv, true).unwrap();
+        assert_eq!(smoothed.0.len(), 1);
+    }
 }
@@ -5,12 +5,12 @@ use geo_types::{
 
 use crate::collections::indexvec::IndexVec;
 use crate::error::check_valid_h3_resolution;
-use crate::{line, Error, H3Cell, Index, ToPolygon};
+use crate::{line, Error, H3Cell, H3DirectedEdge, Index, ToCoordinate, ToPolygon};
 use h3ron_h3_sys::{GeoLoop, GeoPolygon, LatLng};
 use std::os::raw::c_int;
 
 use crate::collections::HashSet;
-use geo::Intersects;
+use geo::{BoundingRect, Contains, Intersects};
 use std::convert::TryInto;
 
 /// convert the geometry to cells at the given resolution
@@ -19,6 +19,35 @@ use std::convert::TryInto;
 /// overlapping input geometries.
 pub trait ToH3Cells {
     fn to_h3_cells(&self, h3_resolution: u8) -> Result<IndexVec<H3Cell>, Error>;
+
+    /// convert the geometry to cells at the given resolution using the given `mode` to
+    /// decide which cells are part of polygonal areas.
+    ///
+    /// The mode has no effect for geometries without an area. Using
+    /// [`ContainmentMode::ContainsCenter`] is equivalent to [`ToH3Cells::to_h3_cells`].
+    fn to_h3_cells_with_mode(
+        &self,
+        h3_resolution: u8,
+        _mode: ContainmentMode,
+    ) -> Result<IndexVec<H3Cell>, Error> {
+        self.to_h3_cells(h3_resolution)
+    }
+}
+
+/// The criteria for cells to be considered part of a polygon.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ContainmentMode {
+    /// Cells whose centroid is located within the polygon.
+    #[default]
+    ContainsCenter,
+
+    /// Cells which are fully contained within the polygon.
+    ContainsFull,
+
+    /// Cells overlapping the polygon, including the cells whose boundary crosses the
+    /// boundary of the polygon while their centroid is located outside. This
+    /// ensures thin polygons are still covered by cells. The cells are returned sorted.
+    Overlapping,
 }
 
 impl ToH3Cells for Polygon<f64> {
@@ -26,13 +55,54 @@ impl ToH3Cells for Polygon<f64> {
         check_valid_h3_resolution(h3_resolution)?;
         polygon_to_cells(self, h3_resolution)
     }
+
+    fn to_h3_cells_with_mode(
+        &self,
+        h3_resolution: u8,
+        mode: ContainmentMode,
+    ) -> Result<IndexVec<H3Cell>, Error> {
+        match mode {
+            ContainmentMode::ContainsCenter => self.to_h3_cells(h3_resolution),
+            ContainmentMode::ContainsFull => {
+                // fully contained cells always have their centroid inside of the polygon
+                let mut outvec = IndexVec::new();
+                for cell in self.to_h3_cells(h3_resolution)?.iter() {
+                    if self.contains(&cell.to_polygon()?) {
+                        outvec.push(cell);
+                    }
+                }
+                Ok(outvec)
+            }
+            ContainmentMode::Overlapping => {
+                check_valid_h3_resolution(h3_resolution)?;
+                // the intersecting cells are collected in a hash set, sort them to return them
+                // in a deterministic order
+                let mut cells = self.to_intersecting_h3_cells(h3_resolution)?;
+                cells.sort_unstable();
+
+                let mut outvec = IndexVec::new();
+                for cell in cells {
+                    outvec.push(cell);
+                }
+                Ok(outvec)
+            }
+        }
+    }
 }
 
 impl ToH3Cells for MultiPolygon<f64> {
     fn to_h3_cells(&self, h3_resolution: u8) -> Result<IndexVec<H3Cell>, Error> {
+        self.to_h3_cells_with_mode(h3_resolution, ContainmentMode::ContainsCenter)
+    }
+
+    fn to_h3_cells_with_mode(
+        &self,
+        h3_resolution: u8,
+        mode: ContainmentMode,
+    ) -> Result<IndexVec<H3Cell>, Error> {
         let mut outvec = IndexVec::new();
         for poly in &self.0 {
-            let mut thisvec = poly.to_h3_cells(h3_resolution)?;
+            let mut thisvec = poly.to_h3_cells_with_mode(h3_resolution, mode)?;
             outvec.append(&mut thisvec);
         }
         Ok(outvec)
@@ -84,12 +154,28 @@ impl ToH3Cells for Rect<f64> {
     fn to_h3_cells(&self, h3_resolution: u8) -> Result<IndexVec<H3Cell>, Error> {
         self.to_polygon().to_h3_cells(h3_resolution)
     }
+
+    fn to_h3_cells_with_mode(
+        &self,
+        h3_resolution: u8,
+        mode: ContainmentMode,
+    ) -> Result<IndexVec<H3Cell>, Error> {
+        self.to_polygon().to_h3_cells_with_mode(h3_resolution, mode)
+    }
 }
 
 impl ToH3Cells for Triangle<f64> {
     fn to_h3_cells(&self, h3_resolution: u8) -> Result<IndexVec<H3Cell>, Error> {
         self.to_polygon().to_h3_cells(h3_resolution)
     }
+
+    fn to_h3_cells_with_mode(
+        &self,
+        h3_resolution: u8,
+        mode: ContainmentMode,
+    ) -> Result<IndexVec<H3Cell>, Error> {
+        self.to_polygon().to_h3_cells_with_mode(h3_resolution, mode)
+    }
 }
 
 impl ToH3Cells for Line<f64> {
@@ -100,9 +186,17 @@ impl ToH3Cells for Line<f64> {
 
 impl ToH3Cells for GeometryCollection<f64> {
     fn to_h3_cells(&self, h3_resolution: u8) -> Result<IndexVec<H3Cell>, Error> {
+        self.to_h3_cells_with_mode(h3_resolution, ContainmentMode::ContainsCenter)
+    }
+
+    fn to_h3_cells_with_mode(
+        &self,
+        h3_resolution: u8,
+        mode: ContainmentMode,
+    ) -> Result<IndexVec<H3Cell>, Error> {
         let mut outvec = IndexVec::new();
         for geom in &self.0 {
-            let mut thisvec = geom.to_h3_cells(h3_resolution)?;
+            let mut thisvec = geom.to_h3_cells_with_mode(h3_resolution, mode)?;
             outvec.append(&mut thisvec);
         }
         Ok(outvec)
@@ -111,17 +205,25 @@ impl ToH3Cells for GeometryCollection<f64> {
 
 impl ToH3Cells for Geometry<f64> {
     fn to_h3_cells(&self, h3_resolution: u8) -> Result<IndexVec<H3Cell>, Error> {
+        self.to_h3_cells_with_mode(h3_resolution, ContainmentMode::ContainsCenter)
+    }
+
+    fn to_h3_cells_with_mode(
+        &self,
+        h3_resolution: u8,
+        mode: ContainmentMode,
+    ) -> Result<IndexVec<H3Cell>, Error> {
         match self {
-            Geometry::Point(pt) => pt.to_h3_cells(h3_resolution),
-            Geometry::Line(l) => l.to_h3_cells(h3_resolution),
-            Geometry::LineString(ls) => ls.to_h3_cells(h3_resolution),
-            Geometry::Polygon(poly) => poly.to_h3_cells(h3_resolution),
-            Geometry::MultiPoint(mp) => mp.to_h3_cells(h3_resolution),
-            Geometry::MultiLineString(mls) => mls.to_h3_cells(h3_resolution),
-            Geometry::MultiPolygon(mpoly) => mpoly.to_h3_cells(h3_resolution),
-            Geometry::GeometryCollection(gc) => gc.to_h3_cells(h3_resolution),
-            Geometry::Rect(r) => r.to_h3_cells(h3_resolution),
-            Geometry::Triangle(tr) => tr.to_h3_cells(h3_resolution),
+            Geometry::Point(pt) => pt.to_h3_cells_with_mode(h3_resolution, mode),
+            Geometry::Line(l) => l.to_h3_cells_with_mode(h3_resolution, mode),
+            Geometry::LineString(ls) => ls.to_h3_cells_with_mode(h3_resolution, mode),
+            Geometry::Polygon(poly) => poly.to_h3_cells_with_mode(h3_resolution, mode),
+            Geometry::MultiPoint(mp) => mp.to_h3_cells_with_mode(h3_resolution, mode),
+            Geometry::MultiLineString(mls) => mls.to_h3_cells_with_mode(h3_resolution, mode),
+            Geometry::MultiPolygon(mpoly) => mpoly.to_h3_cells_with_mode(h3_resolution, mode),
+            Geometry::GeometryCollection(gc) => gc.to_h3_cells_with_mode(h3_resolution, mode),
+            Geometry::Rect(r) => r.to_h3_cells_with_mode(h3_resolution, mode),
+            Geometry::Triangle(tr) => tr.to_h3_cells_with_mode(h3_resolution, mode),
         }
     }
 }
@@ -245,3 +347,390 @@ pub fn polygon_to_cells(poly: &Polygon<f64>, h3_resolution: u8) -> Result<IndexV
         }
     })
 }
+
+/// Lazily fill `poly` with cells tile by tile.
+///
+/// See [`polygon_to_cells_iter`].
+pub struct PolygonToCellsIter<'a> {
+    poly: &'a Polygon<f64>,
+    h3_resolution: u8,
+    bounds: Option<Rect<f64>>,
+    tile_size: f64,
+    num_tiles: (usize, usize),
+    next_tile: usize,
+    cells: std::vec::IntoIter<H3Cell>,
+}
+
+/// approximate number of cells per tile used by [`polygon_to_cells_iter`]
+const POLYGON_TO_CELLS_ITER_TILE_CELLS: f64 = 65536.0;
+
+/// buffer in degrees added around each tile to not miss cells with centroids located
+/// exactly on the boundary of the tile
+const TILE_BUFFER: f64 = 1e-6;
+
+/// maximum size of the tiles in degrees. Polyfilling tiles spanning 180 degrees or more of
+/// longitude fails or misinterprets them as crossing the antimeridian.
+const MAX_TILE_SIZE: f64 = 45.0;
+
+/// Streaming variant of [`polygon_to_cells`] for very large polygons.
+///
+/// The bounding rect of the polygon gets split into tiles which are filled one after the
+/// other when the iterator advances. So only the cells of a single tile are held in
+/// memory at any time. Each cell of the polygon is yielded exactly once.
+pub fn polygon_to_cells_iter(poly: &Polygon<f64>, h3_resolution: u8) -> PolygonToCellsIter<'_> {
+    PolygonToCellsIter::new(poly, h3_resolution, POLYGON_TO_CELLS_ITER_TILE_CELLS)
+}
+
+impl<'a> PolygonToCellsIter<'a> {
+    fn new(poly: &'a Polygon<f64>, h3_resolution: u8, tile_cells: f64) -> Self {
+        let bounds = poly.bounding_rect();
+
+        // the tile size in degrees is derived from the edge length, with one degree
+        // being approx. 111km. Tiles are smaller towards the poles, which is acceptable.
+        let tile_size = H3DirectedEdge::edge_length_avg_m(h3_resolution)
+            .map(|edge_length_m| {
+                (edge_length_m * tile_cells.sqrt() / 111_320.0).clamp(1e-4, MAX_TILE_SIZE)
+            })
+            .unwrap_or(1.0);
+        let num_tiles = bounds.map_or((0, 0), |bounds| {
+            (
+                ((bounds.width() + TILE_BUFFER) / tile_size).ceil().max(1.0) as usize,
+                ((bounds.height() + TILE_BUFFER) / tile_size)
+                    .ceil()
+                    .max(1.0) as usize,
+            )
+        });
+        Self {
+            poly,
+            h3_resolution,
+            bounds,
+            tile_size,
+            num_tiles,
+            next_tile: 0,
+            cells: Vec::new().into_iter(),
+        }
+    }
+
+    /// cells with centroids within the half-open tile `tile_i`
+    fn fill_tile(&self, bounds: &Rect<f64>, tile_i: usize) -> Result<Vec<H3Cell>, Error> {
+        let min = Coord {
+            x: bounds.min().x + (tile_i % self.num_tiles.0) as f64 * self.tile_size,
+            y: bounds.min().y + (tile_i / self.num_tiles.0) as f64 * self.tile_size,
+        };
+        let max = Coord {
+            x: min.x + self.tile_size,
+            y: min.y + self.tile_size,
+        };
+        // the tiles at the edges of the grid may extend beyond the valid coordinate range
+        let buffered_tile = Rect::new(
+            Coord {
+                x: (min.x - TILE_BUFFER).max(-180.0),
+                y: (min.y - TILE_BUFFER).max(-90.0),
+            },
+            Coord {
+                x: (max.x + TILE_BUFFER).min(180.0),
+                y: (max.y + TILE_BUFFER).min(90.0),
+            },
+        );
+        if buffered_tile.width() <= 0.0
+            || buffered_tile.height() <= 0.0
+            || !buffered_tile.intersects(self.poly)
+        {
+            return Ok(vec![]);
+        }
+
+        let mut cells = vec![];
+        for cell in polygon_to_cells(&buffered_tile.to_polygon(), self.h3_resolution)?.iter() {
+            let centroid = cell.to_coordinate()?;
+            if centroid.x >= min.x
+                && centroid.x < max.x
+                && centroid.y >= min.y
+                && centroid.y < max.y
+                && self.poly.intersects(&centroid)
+            {
+                cells.push(cell);
+            }
+        }
+        Ok(cells)
+    }
+}
+
+impl<'a> Iterator for PolygonToCellsIter<'a> {
+    type Item = Result<H3Cell, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(cell) = self.cells.next() {
+                return Some(Ok(cell));
+            }
+            let bounds = self.bounds?;
+            if self.next_tile >= self.num_tiles.0 * self.num_tiles.1 {
+                return None;
+            }
+            let tile_i = self.next_tile;
+            self.next_tile += 1;
+            match self.fill_tile(&bounds, tile_i) {
+                Ok(cells) => self.cells = cells.into_iter(),
+                Err(e) => {
+                    // stop after the first error
+                    self.bounds = None;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+/// All directed edges between the cells filling `poly` at `h3_resolution`.
+///
+/// Edges leaving the region - their destination cell is not part of the filled
+/// polygon - are excluded.
+pub fn edges_in_polygon(
+    poly: &Polygon<f64>,
+    h3_resolution: u8,
+) -> Result<IndexVec<H3DirectedEdge>, Error> {
+    let cells = polygon_to_cells(poly, h3_resolution)?;
+    let cell_set: HashSet<H3Cell> = cells.iter().collect();
+
+    // iterating the filled cells instead of the set keeps the order of the edges stable
+    let mut edges = IndexVec::new();
+    for cell in cells.iter() {
+        for edge in cell.directed_edges()?.iter() {
+            if cell_set.contains(&edge.destination_cell()?) {
+                edges.push(edge);
+            }
+        }
+    }
+    Ok(edges)
+}
+
+/// Find the cell containing `coord` together with the great-circle distance in meters
+/// from `coord` to the centroid of that cell.
+///
+/// Large distances indicate that the resolution is too coarse to represent the coordinate
+/// accurately.
+pub fn cell_with_offset(coord: Coord<f64>, h3_resolution: u8) -> Result<(H3Cell, f64), Error> {
+    let cell = H3Cell::from_coordinate(coord, h3_resolution)?;
+    let mut centroid = LatLng { lat: 0.0, lng: 0.0 };
+    Error::check_returncode(unsafe { h3ron_h3_sys::cellToLatLng(cell.h3index(), &mut centroid) })?;
+    let offset_m = unsafe { h3ron_h3_sys::greatCircleDistanceM(&LatLng::from(coord), &centroid) };
+    Ok((cell, offset_m))
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{Coord, LineString, Polygon};
+
+    use crate::collections::HashSet;
+    use geo::{Contains, Intersects};
+
+    use crate::to_h3::{
+        cell_with_offset, edges_in_polygon, polygon_to_cells, ContainmentMode, PolygonToCellsIter,
+        ToH3Cells,
+    };
+    use crate::{H3Cell, H3DirectedEdge, ToCoordinate, ToPolygon};
+
+    #[test]
+    fn cell_with_offset_below_edge_length() {
+        let coord = Coord::from((12.3456, 45.6789));
+        let (cell, offset_m) = cell_with_offset(coord, 7).unwrap();
+        assert!(offset_m > 0.0);
+        assert!(offset_m < H3DirectedEdge::edge_length_avg_m(7).unwrap() * 1.5);
+        assert!(cell
+            .directed_edges()
+            .unwrap()
+            .iter()
+            .all(|edge| offset_m < edge.length_m().unwrap()));
+
+        // the centroid itself has no offset
+        let (centroid_cell, centroid_offset_m) =
+            cell_with_offset(cell.to_coordinate().unwrap(), 7).unwrap();
+        assert_eq!(centroid_cell, cell);
+        assert!(centroid_offset_m < 1e-6);
+    }
+
+    #[test]
+    fn polygon_to_cells_iter_equals_batch() {
+        let poly = Polygon::new(
+            LineString::from(vec![
+                (12.0, 45.0),
+                (12.6, 45.1),
+                (12.4, 45.5),
+                (12.1, 45.4),
+                (12.0, 45.0),
+            ]),
+            vec![LineString::from(vec![
+                (12.2, 45.2),
+                (12.3, 45.2),
+                (12.3, 45.3),
+                (12.2, 45.2),
+            ])],
+        );
+        let mut expected = polygon_to_cells(&poly, 8)
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+        expected.sort_unstable();
+        assert!(expected.len() > 1000);
+
+        // use small tiles to split the polygon into many tiles
+        let iter = PolygonToCellsIter::new(&poly, 8, 50.0);
+        assert!(iter.num_tiles.0 * iter.num_tiles.1 > 10);
+        let mut streamed = iter.collect::<Result<Vec<H3Cell>, _>>().unwrap();
+        streamed.sort_unstable();
+        assert_eq!(streamed, expected);
+
+        let mut streamed = super::polygon_to_cells_iter(&poly, 8)
+            .collect::<Result<Vec<H3Cell>, _>>()
+            .unwrap();
+        streamed.sort_unstable();
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn polygon_to_cells_iter_equals_batch_low_resolutions() {
+        let rect_polygon = |min: (f64, f64), max: (f64, f64)| {
+            Polygon::new(
+                LineString::from(vec![min, (max.0, min.1), max, (min.0, max.1), min]),
+                vec![],
+            )
+        };
+        let polygons = [
+            rect_polygon((-10.0, 35.0), (30.0, 60.0)),
+            // close to the antimeridian
+            rect_polygon((170.0, -20.0), (179.9, -10.0)),
+        ];
+
+        for poly in polygons.iter() {
+            for h3_resolution in 0..=3 {
+                let mut expected = polygon_to_cells(poly, h3_resolution)
+                    .unwrap()
+                    .iter()
+                    .collect::<Vec<_>>();
+                expected.sort_unstable();
+
+                let mut streamed = super::polygon_to_cells_iter(poly, h3_resolution)
+                    .collect::<Result<Vec<H3Cell>, _>>()
+                    .unwrap();
+                streamed.sort_unstable();
+                assert_eq!(streamed, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn edges_in_polygon_stay_within_region() {
+        let poly = Polygon::new(
+            LineString::from(vec![
+                (12.0, 45.0),
+                (12.1, 45.0),
+                (12.1, 45.1),
+                (12.0, 45.1),
+                (12.0, 45.0),
+            ]),
+            vec![],
+        );
+        let cells = polygon_to_cells(&poly, 8)
+            .unwrap()
+            .iter()
+            .collect::<HashSet<_>>();
+        let edges = edges_in_polygon(&poly, 8).unwrap();
+        assert!(!edges.is_empty());
+
+        let mut num_leaving = 0;
+        for cell in &cells {
+            for edge in cell.directed_edges().unwrap().iter() {
+                let is_contained = edges.iter().any(|e| e == edge);
+                let stays_within = cells.contains(&edge.destination_cell().unwrap());
+                assert_eq!(is_contained, stays_within);
+                if !stays_within {
+                    num_leaving += 1;
+                }
+            }
+        }
+        // the edges at the boundary of the region are excluded
+        assert!(num_leaving > 0);
+        assert_eq!(edges.count(), cells.len() * 6 - num_leaving);
+
+        // the edges follow the order of the filled cells
+        let origin_cells = edges
+            .iter()
+            .map(|edge| edge.origin_cell().unwrap())
+            .collect::<Vec<_>>();
+        let mut filled_cells = polygon_to_cells(&poly, 8)
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+        filled_cells.retain(|cell| origin_cells.contains(cell));
+        let mut origin_cells_dedup = origin_cells.clone();
+        origin_cells_dedup.dedup();
+        assert_eq!(origin_cells_dedup, filled_cells);
+    }
+
+    #[test]
+    fn to_h3_cells_with_containment_mode() {
+        let poly = Polygon::new(
+            LineString::from(vec![
+                (12.0, 45.0),
+                (12.2, 45.0),
+                (12.2, 45.2),
+                (12.0, 45.2),
+                (12.0, 45.0),
+            ]),
+            vec![],
+        );
+        let cells_of = |mode| {
+            poly.to_h3_cells_with_mode(7, mode)
+                .unwrap()
+                .iter()
+                .collect::<HashSet<_>>()
+        };
+        let center = cells_of(ContainmentMode::ContainsCenter);
+        let full = cells_of(ContainmentMode::ContainsFull);
+        let overlapping = cells_of(ContainmentMode::Overlapping);
+
+        assert_eq!(
+            center,
+            poly.to_h3_cells(7).unwrap().iter().collect::<HashSet<_>>()
+        );
+        assert!(full.len() < center.len());
+        assert!(center.len() < overlapping.len());
+        assert!(full.is_subset(&center));
+        assert!(center.is_subset(&overlapping));
+        for cell in &full {
+            assert!(poly.contains(&cell.to_polygon().unwrap()));
+        }
+        for cell in &overlapping {
+            assert!(poly.intersects(&cell.to_polygon().unwrap()));
+        }
+    }
+
+    #[test]
+    fn to_h3_cells_overlapping_thin_polygon() {
+        // much narrower than the cells at resolution 7
+        let poly = Polygon::new(
+            LineString::from(vec![
+                (12.0, 45.0),
+                (12.1, 45.0),
+                (12.1, 45.0001),
+                (12.0, 45.0001),
+                (12.0, 45.0),
+            ]),
+            vec![],
+        );
+        let center = poly
+            .to_h3_cells_with_mode(7, ContainmentMode::ContainsCenter)
+            .unwrap();
+        let overlapping = poly
+            .to_h3_cells_with_mode(7, ContainmentMode::Overlapping)
+            .unwrap();
+        assert!(overlapping.count() > center.count());
+        assert!(overlapping.count() > 5);
+        let overlapping_cells: Vec<_> = overlapping.iter().collect();
+        assert!(overlapping_cells.windows(2).all(|w| w[0] < w[1]));
+        assert!(poly
+            .to_h3_cells_with_mode(7, ContainmentMode::ContainsFull)
+            .unwrap()
+            .is_empty());
+    }
+}
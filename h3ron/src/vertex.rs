@@ -0,0 +1,156 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use geo_types::Coord;
+#[cfg(feature = "use-serde")]
+use serde::{Deserialize, Serialize};
+
+use h3ron_h3_sys::H3Index;
+
+use crate::index::{index_from_str, Index};
+use crate::{Error, FromH3Index, ToCoordinate};
+
+/// H3 Index representing a vertex of a H3 cell.
+///
+/// The vertices shared by neighboring cells are represented by the same index.
+#[derive(PartialOrd, PartialEq, Clone, Hash, Eq, Ord, Copy)]
+#[cfg_attr(feature = "use-serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "use-serde", serde(transparent))]
+#[repr(transparent)]
+pub struct H3Vertex(H3Index);
+
+impl Debug for H3Vertex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "H3Vertex({self})")
+    }
+}
+
+/// convert to index including validation
+impl TryFrom<u64> for H3Vertex {
+    type Error = Error;
+
+    fn try_from(h3index: H3Index) -> Result<Self, Self::Error> {
+        let index = Self::new(h3index);
+        index.validate()?;
+        Ok(index)
+    }
+}
+
+impl FromH3Index for H3Vertex {
+    fn from_h3index(h3index: H3Index) -> Self {
+        Self::new(h3index)
+    }
+}
+
+impl Index for H3Vertex {
+    fn h3index(&self) -> H3Index {
+        self.0
+    }
+
+    fn new(h3index: H3Index) -> Self {
+        Self(h3index)
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if unsafe { h3ron_h3_sys::isValidVertex(self.h3index()) == 0 } {
+            Err(Error::VertexInvalid)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl fmt::Display for H3Vertex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:x}", self.0)
+    }
+}
+
+impl FromStr for H3Vertex {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        index_from_str(s)
+    }
+}
+
+impl ToCoordinate for H3Vertex {
+    type Error = Error;
+
+    /// the coordinate of the vertex
+    fn to_coordinate(&self) -> Result<Coord<f64>, Self::Error> {
+        let mut ll = h3ron_h3_sys::LatLng { lat: 0.0, lng: 0.0 };
+        Error::check_returncode(unsafe { h3ron_h3_sys::vertexToLatLng(self.0, &mut ll) })
+            .map(|_| ll.into())
+    }
+}
+
+impl Deref for H3Vertex {
+    type Target = H3Index;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{H3Cell, Index, ToCoordinate, ToPolygon};
+
+    use super::H3Vertex;
+
+    #[test]
+    fn vertex_validity() {
+        let cell = H3Cell::from_coordinate((12.3, 45.6).into(), 8).unwrap();
+        let vertex = cell.vertex(0).unwrap();
+        assert!(vertex.validate().is_ok());
+        assert!(H3Vertex::try_from(vertex.h3index()).is_ok());
+
+        // a cell is not a vertex
+        assert!(H3Vertex::try_from(cell.h3index()).is_err());
+        assert!(cell.vertex(6).is_err());
+    }
+
+    #[test]
+    fn vertexes_are_shared_by_neighbors() {
+        let cell = H3Cell::from_coordinate((12.3, 45.6).into(), 8).unwrap();
+        let vertexes = cell.vertexes().unwrap();
+        assert_eq!(vertexes.iter().count(), 6);
+        for (vertex_num, vertex) in vertexes.iter().enumerate() {
+            assert_eq!(cell.vertex(vertex_num as u8).unwrap(), vertex);
+        }
+
+        let neighbor = cell.grid_ring_unsafe(1).unwrap().first().unwrap();
+        let shared = neighbor
+            .vertexes()
+            .unwrap()
+            .iter()
+            .filter(|vertex| vertexes.iter().any(|v| v == *vertex))
+            .count();
+        assert_eq!(shared, 2);
+    }
+
+    #[test]
+    fn vertex_coordinate_is_a_cell_boundary_point() {
+        let cell = H3Cell::from_coordinate((12.3, 45.6).into(), 8).unwrap();
+        let boundary = cell.to_polygon().unwrap();
+        for vertex in cell.vertexes().unwrap().iter() {
+            let coord = vertex.to_coordinate().unwrap();
+            assert!(boundary
+                .exterior()
+                .coords()
+                .any(|c| (c.x - coord.x).abs() < 1e-9 && (c.y - coord.y).abs() < 1e-9));
+        }
+    }
+
+    #[test]
+    fn string_roundtrip() {
+        let cell = H3Cell::from_coordinate((12.3, 45.6).into(), 8).unwrap();
+        let vertex = cell.vertex(3).unwrap();
+        assert_eq!(H3Vertex::from_str(&vertex.to_string()).unwrap(), vertex);
+        assert!(H3Vertex::from_str(&cell.to_string()).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+//! Mapping between H3 cells and the tiles of the web-mercator XYZ tiling scheme.
+//!
+//! This allows serving H3 data using existing XYZ tile infrastructure.
+use std::f64::consts::PI;
+
+use geo_types::{Coord, Rect};
+
+use crate::collections::indexvec::IndexVec;
+use crate::{Error, H3Cell, ToH3Cells};
+
+/// The highest supported zoom level.
+pub const MAX_ZOOM: u8 = 30;
+
+/// The northernmost, respectively southernmost, latitude covered by web-mercator.
+const MAX_LATITUDE: f64 = 85.051_128_779_806_6;
+
+/// The maximum longitudinal extent of the rects passed to polyfill.
+const MAX_POLYFILL_WIDTH: f64 = 45.0;
+
+/// Find the XYZ tile at `zoom` containing the coordinate.
+///
+/// Latitudes outside the range covered by web-mercator get clamped to the
+/// northernmost, respectively southernmost, row of tiles.
+pub fn coordinate_to_xyz_tile(coord: Coord<f64>, zoom: u8) -> Result<(u32, u32, u8), Error> {
+    check_valid_zoom(zoom)?;
+    let n = f64::from(1_u32 << zoom);
+    let max_tile = (1_u32 << zoom) - 1;
+
+    let x = ((coord.x + 180.0) / 360.0 * n).floor();
+    let lat_rad = coord.y.to_radians();
+    let y = ((1.0 - (lat_rad.tan() + 1.0 / lat_rad.cos()).ln() / PI) / 2.0 * n).floor();
+
+    Ok((clamp_tile(x, max_tile), clamp_tile(y, max_tile), zoom))
+}
+
+/// The bounds of the XYZ tile in WGS84 coordinates.
+pub fn xyz_tile_bounds(x: u32, y: u32, zoom: u8) -> Result<Rect<f64>, Error> {
+    check_valid_zoom(zoom)?;
+    let num_tiles = 1_u32 << zoom;
+    if x >= num_tiles || y >= num_tiles {
+        return Err(Error::Domain);
+    }
+    let n = f64::from(num_tiles);
+    let lon = |x: f64| x / n * 360.0 - 180.0;
+    let lat = |y: f64| (PI * (1.0 - 2.0 * y / n)).sinh().atan().to_degrees();
+    Ok(Rect::new(
+        Coord {
+            x: lon(f64::from(x)),
+            y: lat(f64::from(y) + 1.0),
+        },
+        Coord {
+            x: lon(f64::from(x) + 1.0),
+            y: lat(f64::from(y)),
+        },
+    ))
+}
+
+/// All cells at `h3_resolution` whose centroid is located within the XYZ tile.
+///
+/// This is the inverse of [`H3Cell::to_xyz_tile`].
+pub fn cells_in_xyz_tile(
+    x: u32,
+    y: u32,
+    zoom: u8,
+    h3_resolution: u8,
+) -> Result<IndexVec<H3Cell>, Error> {
+    let bounds = xyz_tile_bounds(x, y, zoom)?;
+    let max_tile = (1_u32 << zoom) - 1;
+
+    // polyfilling uses great circle arcs between the vertices of the tile, so the
+    // bounds get buffered to not miss any cells along the borders. The candidates are filtered
+    // afterwards.
+    let buffer_x = bounds.width() / 2.0;
+    let buffer_y = bounds.height() / 2.0;
+
+    // cells beyond the latitudes covered by web-mercator belong to the northernmost,
+    // respectively southernmost, row of tiles, so these rows extend to the poles.
+    let min_y = if y == max_tile {
+        -90.0
+    } else {
+        (bounds.min().y - buffer_y).max(-MAX_LATITUDE)
+    };
+    let max_y = if y == 0 {
+        90.0
+    } else {
+        (bounds.max().y + buffer_y).min(MAX_LATITUDE)
+    };
+    let min_x = (bounds.min().x - buffer_x).max(-180.0);
+    let max_x = (bounds.max().x + buffer_x).min(180.0);
+
+    // polyfilling wide rects fails or misinterprets them as crossing the antimeridian, so
+    // they are split into narrower pieces.
+    let num_pieces = ((max_x - min_x) / MAX_POLYFILL_WIDTH).ceil().max(1.0);
+    let piece_width = (max_x - min_x) / num_pieces;
+
+    let mut cells = Vec::new();
+    for piece_i in 0..(num_pieces as usize) {
+        let piece_min_x = (piece_i as f64).mul_add(piece_width, min_x);
+        let piece = Rect::new(
+            Coord {
+                x: piece_min_x,
+                y: min_y,
+            },
+            Coord {
+                x: piece_min_x + piece_width,
+                y: max_y,
+            },
+        );
+        for cell in piece.to_h3_cells(h3_resolution)?.iter() {
+            if cell.to_xyz_tile(zoom)? == (x, y, zoom) {
+                cells.push(cell);
+            }
+        }
+    }
+    // cells along the borders of the pieces may be contained in multiple pieces
+    cells.sort_unstable();
+    cells.dedup();
+
+    let mut index_vec = IndexVec::new();
+    for cell in cells {
+        index_vec.push(cell);
+    }
+    Ok(index_vec)
+}
+
+const fn check_valid_zoom(zoom: u8) -> Result<(), Error> {
+    if zoom > MAX_ZOOM {
+        Err(Error::Domain)
+    } else {
+        Ok(())
+    }
+}
+
+fn clamp_tile(value: f64, max_tile: u32) -> u32 {
+    if value.is_nan() || value < 0.0 {
+        0
+    } else if value > f64::from(max_tile) {
+        max_tile
+    } else {
+        value as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::Contains;
+    use geo_types::{Coord, Point};
+
+    use crate::xyz_tile::{cells_in_xyz_tile, coordinate_to_xyz_tile, xyz_tile_bounds};
+    use crate::{res0_cells, H3Cell, ToCoordinate};
+
+    #[test]
+    fn cell_centroid_within_tile() {
+        let cell = H3Cell::from_coordinate(Coord::from((13.4, 52.5)), 7).unwrap();
+        let (x, y, z) = cell.to_xyz_tile(10).unwrap();
+        assert_eq!(z, 10);
+
+        let bounds = xyz_tile_bounds(x, y, z).unwrap();
+        assert!(bounds.contains(&Point::from(cell.to_coordinate().unwrap())));
+    }
+
+    #[test]
+    fn known_tile() {
+        // Berlin at zoom 10
+        assert_eq!(
+            coordinate_to_xyz_tile(Coord::from((13.4, 52.5)), 10).unwrap(),
+            (550, 335, 10)
+        );
+        assert_eq!(
+            coordinate_to_xyz_tile(Coord::from((0.0, 0.0)), 0).unwrap(),
+            (0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn cells_in_tile_roundtrip() {
+        let cell = H3Cell::from_coordinate(Coord::from((13.4, 52.5)), 7).unwrap();
+        let (x, y, z) = cell.to_xyz_tile(10).unwrap();
+
+        let cells = cells_in_xyz_tile(x, y, z, 7).unwrap();
+        assert!(cells.iter().any(|c| c == cell));
+        for c in cells.iter() {
+            assert_eq!(c.to_xyz_tile(z).unwrap(), (x, y, z));
+        }
+    }
+
+    #[test]
+    fn cells_in_tile_low_zoom() {
+        for h3_resolution in 0..=2 {
+            let all_cells: Vec<_> = res0_cells()
+                .iter()
+                .flat_map(|cell| {
+                    cell.get_children(h3_resolution)
+                        .unwrap()
+                        .iter()
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+
+            for zoom in 0..=2 {
+                let num_tiles = 1_u32 << zoom;
+                let mut num_found = 0;
+                for x in 0..num_tiles {
+                    for y in 0..num_tiles {
+                        let cells = cells_in_xyz_tile(x, y, zoom, h3_resolution).unwrap();
+                        assert!(!cells.is_empty());
+                        for cell in cells.iter() {
+                            assert_eq!(cell.to_xyz_tile(zoom).unwrap(), (x, y, zoom));
+                        }
+                        num_found += cells.count();
+                    }
+                }
+                // each cell is found in exactly one tile - including the cells
+                // located in the polar rows.
+                assert_eq!(num_found, all_cells.len());
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_tile() {
+        assert!(xyz_tile_bounds(4, 0, 2).is_err());
+        assert!(cells_in_xyz_tile(0, 0, 31, 5).is_err());
+    }
+}